@@ -3,11 +3,47 @@ pub mod brand_api;
 pub mod garage_api;
 pub mod vehicle_api;
 pub mod data_info_api;
+pub mod duplicate_api;
+pub mod slot_api;
+pub mod wishlist_api;
+pub mod price_history_api;
+pub mod dlc_api;
+pub mod catalog_diff_api;
+pub mod wiki_import_api;
+pub mod integrity_api;
+pub mod bulk_api;
+pub mod profile_api;
+pub mod share_api;
+pub mod garage_code_api;
+pub mod crew_api;
+pub mod event_api;
+pub mod stats_api;
+pub mod acquisition_api;
+pub mod variant_api;
+pub mod image_api;
 
 // 重新导出所有API函数
 pub use brand_api::*;
 pub use garage_api::*;
 pub use vehicle_api::*;
 pub use data_info_api::*;
+pub use duplicate_api::*;
+pub use slot_api::*;
+pub use wishlist_api::*;
+pub use price_history_api::*;
+pub use dlc_api::*;
+pub use catalog_diff_api::*;
+pub use wiki_import_api::*;
+pub use integrity_api::*;
+pub use bulk_api::*;
+pub use profile_api::*;
+pub use share_api::*;
+pub use garage_code_api::*;
+pub use crew_api::*;
+pub use event_api::*;
+pub use stats_api::*;
+pub use acquisition_api::*;
+pub use variant_api::*;
+pub use image_api::*;
 
 
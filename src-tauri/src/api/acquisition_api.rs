@@ -0,0 +1,191 @@
+// 导入必要的依赖
+use std::collections::HashMap;
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::price_history_api::{load_kind_prices, set_kind_price};
+use crate::database::create_vehicle_acquisition_table;
+use crate::models::{ApiResponse, VehicleAcquisition, VehicleFilter, VehicleOverview};
+
+// 可获取状态：可直接购买、限时获取（领奖台、幸运转盘、轮换）、需完成任务解锁、已下架
+pub const OBTAINABILITY_STATUSES: [&str; 4] = ["available", "limited", "unlock_required", "removed"];
+
+// 没有获取方式数据的载具在筛选时使用的状态
+pub const UNKNOWN_OBTAINABILITY: &str = "unknown";
+
+// 读取所有载具的获取方式，交易价格来自vehicle_price表
+pub(crate) fn load_vehicle_acquisitions(conn: &Connection) -> Result<HashMap<String, VehicleAcquisition>> {
+    create_vehicle_acquisition_table(conn)?;
+    let mut stmt = conn.prepare("SELECT vehicle_id, store, unlock_condition, obtainability FROM vehicle_acquisition")?;
+    let acquisition_iter = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            VehicleAcquisition {
+                store: row.get(1)?,
+                trade_price: None,
+                unlock_condition: row.get(2)?,
+                obtainability: row.get(3)?,
+            },
+        ))
+    })?;
+    let mut acquisitions = acquisition_iter.collect::<Result<HashMap<_, _>>>()?;
+    for (vehicle_id, price) in load_kind_prices(conn, "trade")? {
+        acquisitions.entry(vehicle_id).or_default().trade_price = Some(price);
+    }
+    Ok(acquisitions)
+}
+
+// 为载具补充获取方式
+pub(crate) fn attach_acquisitions(conn: &Connection, vehicles: &mut [VehicleOverview]) -> Result<()> {
+    let mut acquisitions = load_vehicle_acquisitions(conn)?;
+    for vehicle in vehicles.iter_mut() {
+        vehicle.acquisition = acquisitions.remove(&vehicle.id);
+    }
+    Ok(())
+}
+
+// 判断载具是否满足获取方式筛选条件，商店名称不区分大小写
+pub(crate) fn acquisition_matches(vehicle: &VehicleOverview, filter: &VehicleFilter) -> bool {
+    let acquisition = vehicle.acquisition.clone().unwrap_or_default();
+    if let Some(stores) = filter.stores.as_ref().filter(|stores| !stores.is_empty()) {
+        let store = acquisition.store.as_deref().unwrap_or("");
+        if !stores.iter().any(|wanted| wanted.trim().eq_ignore_ascii_case(store)) {
+            return false;
+        }
+    }
+    if let Some(statuses) = filter.obtainability.as_ref().filter(|statuses| !statuses.is_empty()) {
+        let status = acquisition.obtainability.as_deref().unwrap_or(UNKNOWN_OBTAINABILITY);
+        if !statuses.iter().any(|wanted| wanted == status) {
+            return false;
+        }
+    }
+    if let Some(requires_unlock) = filter.requires_unlock {
+        let has_condition = acquisition
+            .unlock_condition
+            .as_deref()
+            .map(|condition| !condition.trim().is_empty())
+            .unwrap_or(false);
+        if has_condition != requires_unlock {
+            return false;
+        }
+    }
+    if let Some(has_trade_price) = filter.has_trade_price {
+        if acquisition.trade_price.is_some() != has_trade_price {
+            return false;
+        }
+    }
+    true
+}
+
+// 设置载具的获取方式，acquisition为空时删除
+#[command]
+pub fn set_vehicle_acquisition(
+    app: AppHandle,
+    vehicle_id: String,
+    acquisition: Option<VehicleAcquisition>,
+) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db"))
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| run_set_vehicle_acquisition(&mut conn, &vehicle_id, acquisition.as_ref()));
+
+    match result {
+        Ok(_) => Ok(ApiResponse {
+            success: true,
+            data: None,
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_set_vehicle_acquisition(
+    conn: &mut Connection,
+    vehicle_id: &str,
+    acquisition: Option<&VehicleAcquisition>,
+) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    create_vehicle_acquisition_table(&tx).map_err(|e| e.to_string())?;
+    let acquisition = match acquisition {
+        Some(acquisition) => acquisition,
+        None => {
+            tx.execute("DELETE FROM vehicle_acquisition WHERE vehicle_id = ?1", params![vehicle_id])
+                .map_err(|e| e.to_string())?;
+            set_kind_price(&tx, vehicle_id, "trade", None)?;
+            return tx.commit().map_err(|e| e.to_string());
+        }
+    };
+
+    let exists = tx
+        .query_row("SELECT 1 FROM vehicle_overview WHERE id = ?1", params![vehicle_id], |_| Ok(()))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if exists.is_none() {
+        return Err(format!("载具{}不存在", vehicle_id));
+    }
+    if let Some(status) = acquisition.obtainability.as_deref() {
+        if !OBTAINABILITY_STATUSES.contains(&status) {
+            return Err(format!(
+                "可获取状态{}无效，可选值为{}",
+                status,
+                OBTAINABILITY_STATUSES.join("、")
+            ));
+        }
+    }
+
+    tx.execute(
+        "INSERT OR REPLACE INTO vehicle_acquisition (vehicle_id, store, unlock_condition, obtainability)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            vehicle_id,
+            acquisition.store,
+            acquisition.unlock_condition,
+            acquisition.obtainability,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    // 交易价格与其他价格种类一起保存，并记录价格历史
+    set_kind_price(&tx, vehicle_id, "trade", acquisition.trade_price)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// 获取所有已使用的商店名称，供筛选下拉框使用
+#[command]
+pub fn get_vehicle_stores(app: AppHandle) -> Result<ApiResponse<Vec<String>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| {
+        create_vehicle_acquisition_table(&conn)?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT store FROM vehicle_acquisition WHERE store IS NOT NULL AND store != '' ORDER BY store",
+        )?;
+        let store_iter = stmt.query_map([], |row| row.get(0))?;
+        store_iter.collect::<Result<Vec<String>>>()
+    });
+
+    match result {
+        Ok(stores) => Ok(ApiResponse {
+            success: true,
+            data: Some(stores),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
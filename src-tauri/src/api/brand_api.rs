@@ -1,11 +1,25 @@
 // 导入必要的依赖
-use rusqlite::{params, Connection, Result};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Result};
+use serde_json::json;
 use tauri::{command, AppHandle, Manager};
 
 // 导入数据模型
-use crate::models::{ApiResponse, VehicleBrand};
+use crate::api::garage_api::{
+    attach_user_db, load_garages, load_garages_from, parse_vehicle_list, rewrite_garage_slots, slot_vehicle_id,
+};
+use crate::api::image_api::{remove_image_files, vehicle_image_file};
+use crate::api::integrity_api::parse_vehicle_id;
+use crate::api::vehicle_api::{
+    delete_vehicle_side_data, delete_vehicle_wishlist, load_vehicle_overviews, next_vehicle_id, rekey_vehicle_in,
+};
+use crate::database::{create_brand_meta_table, has_column};
+use crate::models::{AffectedSlot, ApiResponse, BrandDeleteReport, BrandMergeReport, VehicleBrand};
 
-// 获取所有载具品牌
+// 获取所有载具品牌（包含元数据、载具数量和已拥有数量）
 #[command]
 pub fn get_vehicle_brands(app: AppHandle) -> Result<ApiResponse<Vec<VehicleBrand>>, String> {
     let app_dir = app
@@ -15,39 +29,27 @@ pub fn get_vehicle_brands(app: AppHandle) -> Result<ApiResponse<Vec<VehicleBrand
     let db_path = app_dir.join("gtavm_common.db"); // 使用通用数据库文件存储品牌数据
 
     match Connection::open(&db_path) {
-        Ok(conn) => {
-            match conn.prepare("SELECT id, brand_name, brand_name_en, remarks FROM vehicle_brand") {
-                Ok(mut stmt) => {
-                    match stmt.query_map([], |row| {
-                        Ok(VehicleBrand {
-                            id: row.get(0)?,
-                            brand_name: row.get(1)?,
-                            brand_name_en: row.get(2)?,
-                            remarks: row.get(3)?,
-                        })
-                    }) {
-                        Ok(brand_iter) => match brand_iter.collect::<Result<_>>() {
-                            Ok(brands) => Ok(ApiResponse {
-                                success: true,
-                                data: Some(brands),
-                                error: None,
-                            }),
-                            Err(e) => Ok(ApiResponse {
-                                success: false,
-                                data: None,
-                                error: Some(e.to_string()),
-                            }),
-                        },
-                        Err(e) => Ok(ApiResponse {
-                            success: false,
-                            data: None,
-                            error: Some(e.to_string()),
-                        }),
-                    }
+        Ok(conn) => match load_vehicle_brands(&conn) {
+            Ok(mut brands) => {
+                // 统计车库中每个品牌的载具数量，用户库只读打开，不可用时不影响品牌列表
+                let owned_counts = Connection::open_with_flags(app_dir.join("gtavm_user.db"), OpenFlags::SQLITE_OPEN_READ_ONLY)
+                    .map(|user_conn| count_owned_by_brand(&conn, &user_conn))
+                    .unwrap_or_default();
+                for brand in &mut brands {
+                    brand.owned_count = Some(owned_counts.get(&brand.id).copied().unwrap_or(0));
                 }
-                Err(e) => Err(e.to_string()),
+                Ok(ApiResponse {
+                    success: true,
+                    data: Some(brands),
+                    error: None,
+                })
             }
-        }
+            Err(e) => Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            }),
+        },
         Err(e) => Ok(ApiResponse {
             success: false,
             data: None,
@@ -68,10 +70,25 @@ pub fn add_vehicle_brand(
         .expect("Failed to get app data directory");
     let db_path = app_dir.join("gtavm_common.db"); // 使用通用数据库文件存储品牌数据
 
+    if let Err(e) = validate_brand_meta(&app_dir, &brand) {
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        });
+    }
+
     match Connection::open(&db_path) {
         Ok(mut conn) => {
             match conn.transaction() {
                 Ok(tx) => {
+                    if let Err(e) = check_parent_brand(&tx, brand.id, brand.parent_brand_id) {
+                        return Ok(ApiResponse {
+                            success: false,
+                            data: None,
+                            error: Some(e),
+                        });
+                    }
                     match tx.execute(
                         "INSERT INTO vehicle_brand (id, brand_name, brand_name_en, remarks) 
                          VALUES (?1, ?2, ?3, ?4)",
@@ -81,7 +98,8 @@ pub fn add_vehicle_brand(
                             brand.brand_name_en,
                             brand.remarks
                         ],
-                    ) {
+                    )
+                    .and_then(|_| save_brand_meta(&tx, &brand)) {
                         Ok(_) => match tx.commit() {
                             Ok(_) => Ok(ApiResponse {
                                 success: true,
@@ -132,10 +150,25 @@ pub fn update_vehicle_brand(
         .expect("Failed to get app data directory");
     let db_path = app_dir.join("gtavm_common.db"); // 使用通用数据库文件存储品牌数据
 
+    if let Err(e) = validate_brand_meta(&app_dir, &brand) {
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        });
+    }
+
     match Connection::open(&db_path) {
         Ok(mut conn) => {
             match conn.transaction() {
                 Ok(tx) => {
+                    if let Err(e) = check_parent_brand(&tx, brand.id, brand.parent_brand_id) {
+                        return Ok(ApiResponse {
+                            success: false,
+                            data: None,
+                            error: Some(e),
+                        });
+                    }
                     match tx.execute(
                         "UPDATE vehicle_brand SET brand_name = ?1, brand_name_en = ?2, remarks = ?3 WHERE id = ?4",
                         params![
@@ -144,7 +177,8 @@ pub fn update_vehicle_brand(
                             brand.remarks,
                             brand.id
                         ],
-                    ) {
+                    )
+                    .and_then(|_| save_brand_meta(&tx, &brand)) {
                         Ok(_) => {
                             match tx.commit() {
                                 Ok(_) => {
@@ -190,73 +224,422 @@ pub fn update_vehicle_brand(
 }
 
 // 删除载具品牌
+// cascade为true时一并删除该品牌下未被车库拥有的载具；存在已拥有的载具时拒绝删除并返回所在车位
 #[command]
-pub fn delete_vehicle_brand(app: AppHandle, id: i32) -> Result<ApiResponse<()>, String> {
+pub fn delete_vehicle_brand(
+    app: AppHandle,
+    id: i32,
+    cascade: Option<bool>,
+) -> Result<ApiResponse<BrandDeleteReport>, String> {
     let app_dir = app
         .path()
         .app_data_dir()
         .expect("Failed to get app data directory");
-    let db_path = app_dir.join("gtavm_common.db"); // 使用通用数据库文件存储品牌数据
 
-    match Connection::open(&db_path) {
-        Ok(mut conn) => {
-            match conn.transaction() {
-                Ok(tx) => {
-                    // 先检查是否有载具关联到该品牌
-                    let count: i64 = match tx.query_row(
-                        "SELECT COUNT(*) FROM vehicle_overview WHERE brand_id = ?1",
-                        params![id],
-                        |row| Ok(row.get(0).unwrap_or(0)),
-                    ) {
-                        Ok(count) => count,
-                        Err(e) => {
-                            return Ok(ApiResponse {
-                                success: false,
-                                data: None,
-                                error: Some(e.to_string()),
-                            })
-                        }
-                    };
+    match run_delete_brand(&app_dir, id, cascade.unwrap_or(false)) {
+        Ok((report, None)) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Ok((report, Some(reason))) => Ok(ApiResponse {
+            success: false,
+            data: Some(report),
+            error: Some(reason),
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
 
-                    if count > 0 {
-                        return Err("该品牌下存在载具，无法删除".to_string());
-                    }
+// 执行品牌删除，第二个返回值为拒绝删除的原因（此时不做任何修改）
+fn run_delete_brand(app_dir: &Path, id: i32, cascade: bool) -> Result<(BrandDeleteReport, Option<String>), String> {
+    let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    attach_user_db(&conn, app_dir).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
 
-                    match tx.execute("DELETE FROM vehicle_brand WHERE id = ?1", params![id]) {
-                        Ok(_) => match tx.commit() {
-                            Ok(_) => Ok(ApiResponse {
-                                success: true,
-                                data: None,
-                                error: None,
-                            }),
-                            Err(e) => Ok(ApiResponse {
-                                success: false,
-                                data: None,
-                                error: Some(e.to_string()),
-                            }),
-                        },
-                        Err(e) => {
-                            // 回滚事务
-                            let _ = tx.rollback();
-                            Ok(ApiResponse {
-                                success: false,
-                                data: None,
-                                error: Some(e.to_string()),
-                            })
-                        }
-                    }
-                }
-                Err(e) => Ok(ApiResponse {
-                    success: false,
-                    data: None,
-                    error: Some(e.to_string()),
-                }),
-            }
-        }
+    let vehicle_ids = brand_vehicle_ids(&tx, id).map_err(|e| e.to_string())?;
+    let blocking_slots: Vec<AffectedSlot> = load_garages_from(&tx, "user_db")
+        .map_err(|e| e.to_string())?
+        .iter()
+        .flat_map(|garage| {
+            let slots = parse_vehicle_list(garage.vehicle_list.as_deref()).unwrap_or_default();
+            slots
+                .iter()
+                .enumerate()
+                .filter_map(|(slot_index, slot)| {
+                    slot_vehicle_id(slot)
+                        .filter(|vehicle_id| vehicle_ids.contains(vehicle_id))
+                        .map(|vehicle_id| AffectedSlot {
+                            garage_id: garage.id.unwrap_or_default(),
+                            garage_name: garage.garage_name.clone(),
+                            slot: slot_index,
+                            vehicle_id,
+                            change_kind: "owned".to_string(),
+                        })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    if !vehicle_ids.is_empty() && (!cascade || !blocking_slots.is_empty()) {
+        let reason = if cascade {
+            format!("该品牌下有{}个车位中的载具已被拥有，无法删除", blocking_slots.len())
+        } else {
+            "该品牌下存在载具，无法删除".to_string()
+        };
+        return Ok((
+            BrandDeleteReport {
+                deleted_vehicles: Vec::new(),
+                blocking_slots,
+            },
+            Some(reason),
+        ));
+    }
+
+    // 目录侧表中按载具ID保存的数据和愿望清单条目随载具一起删除，图片文件在事务提交后删除
+    let mut image_files = Vec::new();
+    for vehicle_id in &vehicle_ids {
+        image_files.extend(vehicle_image_file(&tx, vehicle_id).map_err(|e| e.to_string())?);
+        delete_vehicle_side_data(&tx, vehicle_id)
+            .and_then(|_| delete_vehicle_wishlist(&tx, vehicle_id))
+            .map_err(|e| e.to_string())?;
+    }
+    tx.execute("DELETE FROM vehicle_overview WHERE brand_id = ?1", params![id])
+        .and_then(|_| tx.execute("DELETE FROM vehicle_brand WHERE id = ?1", params![id]))
+        .and_then(|_| create_brand_meta_table(&tx))
+        .and_then(|_| tx.execute("DELETE FROM vehicle_brand_meta WHERE brand_id = ?1", params![id]))
+        // 以该品牌为上级品牌的品牌改为没有上级品牌
+        .and_then(|_| {
+            tx.execute(
+                "UPDATE vehicle_brand_meta SET parent_brand_id = NULL WHERE parent_brand_id = ?1",
+                params![id],
+            )
+        })
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    for image_file in &image_files {
+        remove_image_files(app_dir, image_file);
+    }
+
+    Ok((
+        BrandDeleteReport {
+            deleted_vehicles: vehicle_ids,
+            blocking_slots: Vec::new(),
+        },
+        None,
+    ))
+}
+
+// 合并品牌：将来源品牌的载具移至目标品牌并按目标品牌重新分配ID，同步更新车库中的载具ID和品牌名称，并删除来源品牌
+#[command]
+pub fn merge_brands(app: AppHandle, source_id: i32, target_id: i32) -> Result<ApiResponse<BrandMergeReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_merge_brands(&app_dir, source_id, target_id) {
+        Ok(report) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
         Err(e) => Ok(ApiResponse {
             success: false,
             data: None,
-            error: Some(e.to_string()),
+            error: Some(e),
         }),
     }
 }
+
+fn run_merge_brands(app_dir: &Path, source_id: i32, target_id: i32) -> Result<BrandMergeReport, String> {
+    if source_id == target_id {
+        return Err("来源品牌与目标品牌不能相同".to_string());
+    }
+
+    let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    attach_user_db(&conn, app_dir).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let brand_names = |id: i32| -> Result<(Option<String>, Option<String>), String> {
+        tx.query_row(
+            "SELECT brand_name, brand_name_en FROM vehicle_brand WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("品牌不存在: {}", id))
+    };
+    brand_names(source_id)?;
+    let (target_name, target_name_en) = brand_names(target_id)?;
+
+    let mut moved_vehicles = brand_vehicle_ids(&tx, source_id).map_err(|e| e.to_string())?;
+    moved_vehicles.sort_by_key(|id| parse_vehicle_id(id));
+
+    // 载具ID中编码了品牌，按目标品牌重新分配ID（同时更新载具品牌、车位ID和品牌名称）
+    let mut rekeyed = Vec::new();
+    let mut updated_slots = Vec::new();
+    for vehicle_id in moved_vehicles.iter().filter(|id| parse_vehicle_id(id).is_some()) {
+        let new_id = next_vehicle_id(&tx, target_id).map_err(|e| e.to_string())?;
+        let mut report = rekey_vehicle_in(&tx, vehicle_id, &new_id, Some(source_id))?;
+        updated_slots.append(&mut report.updated_slots);
+        rekeyed.push(report);
+    }
+
+    // 不符合"品牌ID-序号"格式的旧ID保持不变，只修改品牌
+    tx.execute(
+        "UPDATE vehicle_overview SET brand_id = ?1, version = version + 1 WHERE brand_id = ?2",
+        params![target_id, source_id],
+    )
+    .and_then(|_| create_brand_meta_table(&tx))
+    .and_then(|_| {
+        tx.execute(
+            "UPDATE vehicle_brand_meta SET parent_brand_id = ?1 WHERE parent_brand_id = ?2",
+            params![target_id, source_id],
+        )
+    })
+    // 目标品牌合并后不能以自身为上级品牌
+    .and_then(|_| {
+        tx.execute(
+            "UPDATE vehicle_brand_meta SET parent_brand_id = NULL WHERE brand_id = ?1 AND parent_brand_id = ?1",
+            params![target_id],
+        )
+    })
+    .and_then(|_| tx.execute("DELETE FROM vehicle_brand_meta WHERE brand_id = ?1", params![source_id]))
+    .and_then(|_| tx.execute("DELETE FROM vehicle_brand WHERE id = ?1", params![source_id]))
+    .map_err(|e| e.to_string())?;
+    // 来源品牌的下级品牌改挂到目标品牌后，目标品牌的上级品牌链可能经过这些品牌形成循环
+    let target_parent = parent_brand_id(&tx, target_id).map_err(|e| e.to_string())?;
+    check_parent_brand(&tx, target_id, target_parent)
+        .map_err(|e| format!("合并后品牌关系无效，请先调整上级品牌: {}", e))?;
+
+    let moved: HashSet<&String> = moved_vehicles.iter().filter(|id| parse_vehicle_id(id).is_none()).collect();
+    let mut renamed_slots = rewrite_garage_slots(&tx, "user_db", "brand_merged", |slot| {
+        let owned = slot
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(|id| moved.contains(&id.to_string()))
+            .unwrap_or(false);
+        if owned {
+            slot.insert("brandName".to_string(), json!(target_name));
+            slot.insert("brandNameEn".to_string(), json!(target_name_en));
+        }
+        owned
+    })
+    .map_err(|e| e.to_string())?;
+    updated_slots.append(&mut renamed_slots);
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(BrandMergeReport {
+        moved_vehicles,
+        rekeyed,
+        updated_slots,
+    })
+}
+
+// 获取品牌下所有载具ID
+fn brand_vehicle_ids(conn: &Connection, brand_id: i32) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT id FROM vehicle_overview WHERE brand_id = ?1 ORDER BY id")?;
+    let id_iter = stmt.query_map(params![brand_id], |row| row.get(0))?;
+    id_iter.collect()
+}
+
+// 读取所有载具品牌，包含元数据和目录中的载具数量
+// 候选目录等没有元数据表的数据库中元数据为空
+pub(crate) fn load_vehicle_brands(conn: &Connection) -> Result<Vec<VehicleBrand>> {
+    let sql = if has_column(conn, "vehicle_brand_meta", "brand_id") {
+        "SELECT b.id, b.brand_name, b.brand_name_en, b.remarks, m.logo_path, m.country, m.real_world_brand, m.parent_brand_id,
+                (SELECT COUNT(*) FROM vehicle_overview v WHERE v.brand_id = b.id)
+         FROM vehicle_brand b
+         LEFT JOIN vehicle_brand_meta m ON m.brand_id = b.id"
+    } else {
+        "SELECT b.id, b.brand_name, b.brand_name_en, b.remarks, NULL, NULL, NULL, NULL,
+                (SELECT COUNT(*) FROM vehicle_overview v WHERE v.brand_id = b.id)
+         FROM vehicle_brand b"
+    };
+    let mut stmt = conn.prepare(sql)?;
+    let brand_iter = stmt.query_map([], |row| {
+        Ok(VehicleBrand {
+            id: row.get(0)?,
+            brand_name: row.get(1)?,
+            brand_name_en: row.get(2)?,
+            remarks: row.get(3)?,
+            logo_path: row.get(4)?,
+            country: row.get(5)?,
+            real_world_brand: row.get(6)?,
+            parent_brand_id: row.get(7)?,
+            vehicle_count: Some(row.get(8)?),
+            owned_count: None,
+        })
+    })?;
+    brand_iter.collect()
+}
+
+// 统计车库中每个品牌的载具数量（按车位计数），user_conn为调用方以只读方式打开的用户库
+pub(crate) fn count_owned_by_brand(common_conn: &Connection, user_conn: &Connection) -> HashMap<i32, i64> {
+    let mut counts = HashMap::new();
+    let garages = match load_garages(user_conn) {
+        Ok(garages) => garages,
+        Err(_) => return counts,
+    };
+    let brand_by_vehicle: HashMap<String, i32> = match load_vehicle_overviews(common_conn) {
+        Ok(vehicles) => vehicles
+            .into_iter()
+            .filter_map(|v| v.brand_id.map(|brand_id| (v.id, brand_id)))
+            .collect(),
+        Err(_) => return counts,
+    };
+    for garage in &garages {
+        let slots = match parse_vehicle_list(garage.vehicle_list.as_deref()) {
+            Ok(slots) => slots,
+            Err(_) => continue,
+        };
+        for vehicle_id in slots.iter().filter_map(slot_vehicle_id) {
+            if let Some(brand_id) = brand_by_vehicle.get(&vehicle_id) {
+                *counts.entry(*brand_id).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+// 查找资源文件：支持绝对路径，相对路径依次在应用数据目录和前端public目录中查找
+pub(crate) fn resolve_asset_path(app_dir: &Path, asset_path: &str) -> Option<PathBuf> {
+    let path = Path::new(asset_path.trim_start_matches('/'));
+    if Path::new(asset_path).is_absolute() && Path::new(asset_path).exists() {
+        return Some(PathBuf::from(asset_path));
+    }
+    let mut roots = vec![app_dir.to_path_buf()];
+    if let Ok(current_dir) = env::current_dir() {
+        roots.push(current_dir.join("public"));
+        roots.push(current_dir.join("..").join("public"));
+    }
+    if let Some(exe_dir) = env::current_exe().ok().and_then(|exe| exe.parent().map(|dir| dir.to_path_buf())) {
+        roots.push(exe_dir);
+    }
+    roots.into_iter().map(|root| root.join(path)).find(|candidate| candidate.is_file())
+}
+
+// 校验品牌元数据：标志文件必须存在，上级品牌不能是自身
+// 上级品牌是否存在以及是否形成循环需要读取数据库，在事务中由check_parent_brand校验
+fn validate_brand_meta(app_dir: &Path, brand: &VehicleBrand) -> Result<(), String> {
+    if let Some(logo_path) = brand.logo_path.as_deref().filter(|p| !p.is_empty()) {
+        if resolve_asset_path(app_dir, logo_path).is_none() {
+            return Err(format!("品牌标志文件不存在: {}", logo_path));
+        }
+    }
+    if brand.parent_brand_id == Some(brand.id) {
+        return Err("上级品牌不能是品牌自身".to_string());
+    }
+    Ok(())
+}
+
+// 读取品牌的上级品牌
+fn parent_brand_id(conn: &Connection, brand_id: i32) -> Result<Option<i32>> {
+    create_brand_meta_table(conn)?;
+    conn.query_row(
+        "SELECT parent_brand_id FROM vehicle_brand_meta WHERE brand_id = ?1",
+        params![brand_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map(Option::flatten)
+}
+
+// 校验上级品牌：必须存在，且沿上级品牌链向上不能回到品牌自身
+fn check_parent_brand(conn: &Connection, brand_id: i32, parent: Option<i32>) -> Result<(), String> {
+    let mut current = match parent {
+        Some(parent) => parent,
+        None => return Ok(()),
+    };
+    let exists = conn
+        .query_row("SELECT 1 FROM vehicle_brand WHERE id = ?1", params![current], |_| Ok(()))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .is_some();
+    if !exists {
+        return Err(format!("上级品牌不存在: {}", current));
+    }
+
+    // 已有数据中不经过该品牌的循环不在此处处理，遇到重复的品牌即停止
+    let mut visited = HashSet::new();
+    loop {
+        if current == brand_id {
+            return Err("上级品牌关系不能形成循环".to_string());
+        }
+        if !visited.insert(current) {
+            return Ok(());
+        }
+        match parent_brand_id(conn, current).map_err(|e| e.to_string())? {
+            Some(next) => current = next,
+            None => return Ok(()),
+        }
+    }
+}
+
+// 保存品牌元数据
+fn save_brand_meta(conn: &Connection, brand: &VehicleBrand) -> Result<usize> {
+    create_brand_meta_table(conn)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO vehicle_brand_meta (brand_id, logo_path, country, real_world_brand, parent_brand_id)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            brand.id,
+            brand.logo_path,
+            brand.country,
+            brand.real_world_brand,
+            brand.parent_brand_id
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::catalog_db;
+
+    fn add_brand(conn: &Connection, id: i32, parent: Option<i32>) {
+        conn.execute("INSERT INTO vehicle_brand (id, brand_name) VALUES (?1, ?1)", params![id]).unwrap();
+        conn.execute(
+            "INSERT INTO vehicle_brand_meta (brand_id, parent_brand_id) VALUES (?1, ?2)",
+            params![id, parent],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn parent_must_exist() {
+        let conn = catalog_db();
+        add_brand(&conn, 1, None);
+        assert!(check_parent_brand(&conn, 1, None).is_ok());
+        assert!(check_parent_brand(&conn, 1, Some(99)).is_err());
+    }
+
+    #[test]
+    fn parent_chain_must_not_return_to_brand() {
+        let conn = catalog_db();
+        add_brand(&conn, 1, None);
+        add_brand(&conn, 2, Some(1));
+        add_brand(&conn, 3, Some(2));
+        // 3 -> 2 -> 1 是合法的链
+        assert!(check_parent_brand(&conn, 4, Some(3)).is_ok());
+        // 1 -> 3 -> 2 -> 1 形成循环
+        assert!(check_parent_brand(&conn, 1, Some(3)).is_err());
+        // 1 -> 2 -> 1 形成循环
+        assert!(check_parent_brand(&conn, 1, Some(2)).is_err());
+    }
+
+    #[test]
+    fn existing_cycle_elsewhere_does_not_hang() {
+        let conn = catalog_db();
+        add_brand(&conn, 1, Some(2));
+        add_brand(&conn, 2, Some(1));
+        add_brand(&conn, 3, None);
+        assert!(check_parent_brand(&conn, 3, Some(1)).is_ok());
+    }
+}
@@ -0,0 +1,134 @@
+// 导入必要的依赖
+use std::path::Path;
+
+use rusqlite::Connection;
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::garage_api::attach_user_db;
+use crate::api::vehicle_api::{apply_vehicle_patch, insert_vehicle, load_catalog_refs, CatalogRefs};
+use crate::database::{record_price_changes, sync_dlc_releases};
+use crate::models::{ApiResponse, BulkResult, BulkRowResult, VehicleOverview, VehiclePatch};
+
+// 在一个事务中逐行处理，每行使用独立的保存点，失败的行不影响其他行
+// all_or_nothing为true时任意一行失败则全部回滚
+fn run_bulk<T, F>(app_dir: &Path, rows: &[T], row_ids: Vec<Option<String>>, all_or_nothing: bool, apply: F) -> Result<BulkResult, String>
+where
+    F: Fn(&Connection, &CatalogRefs, &T) -> Result<String, String>,
+{
+    let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    // 更换品牌会重编号车库中的载具引用
+    attach_user_db(&conn, app_dir).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let refs = load_catalog_refs(&tx).map_err(|e| e.to_string())?;
+
+    let mut results = Vec::with_capacity(rows.len());
+    for (index, (row, row_id)) in rows.iter().zip(row_ids).enumerate() {
+        tx.execute_batch("SAVEPOINT bulk_row").map_err(|e| e.to_string())?;
+        match apply(&tx, &refs, row) {
+            Ok(id) => {
+                tx.execute_batch("RELEASE bulk_row").map_err(|e| e.to_string())?;
+                results.push(BulkRowResult {
+                    index,
+                    id: Some(id),
+                    success: true,
+                    error: None,
+                });
+            }
+            Err(e) => {
+                tx.execute_batch("ROLLBACK TO bulk_row; RELEASE bulk_row")
+                    .map_err(|e| e.to_string())?;
+                results.push(BulkRowResult {
+                    index,
+                    id: row_id,
+                    success: false,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+
+    let applied = results.iter().filter(|result| result.success).count();
+    if all_or_nothing && applied < results.len() {
+        tx.rollback().map_err(|e| e.to_string())?;
+        return Ok(BulkResult {
+            results,
+            applied: 0,
+            committed: false,
+        });
+    }
+
+    // 记录价格变化并为新载具补充所属DLC
+    record_price_changes(&tx, true)
+        .and_then(|_| sync_dlc_releases(&tx))
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(BulkResult {
+        results,
+        applied,
+        committed: true,
+    })
+}
+
+// 批量更新载具，每个补丁只更新提供的字段
+// 每个补丁都需带版本号，ignore_versions为true时显式跳过版本校验（如脚本批量修正价格）
+#[command]
+pub fn bulk_update_vehicles(
+    app: AppHandle,
+    patches: Vec<VehiclePatch>,
+    all_or_nothing: Option<bool>,
+    ignore_versions: Option<bool>,
+) -> Result<ApiResponse<BulkResult>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let row_ids = patches.iter().map(|patch| Some(patch.id.clone())).collect();
+    let ignore_versions = ignore_versions.unwrap_or(false);
+    let apply = |conn: &Connection, refs: &CatalogRefs, patch: &VehiclePatch| {
+        apply_vehicle_patch(conn, refs, patch, ignore_versions)
+    };
+    match run_bulk(&app_dir, &patches, row_ids, all_or_nothing.unwrap_or(false), apply) {
+        Ok(result) => Ok(ApiResponse {
+            success: true,
+            data: Some(result),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 批量新增载具，未指定ID的行按品牌自动分配ID
+#[command]
+pub fn bulk_add_vehicles(
+    app: AppHandle,
+    rows: Vec<VehicleOverview>,
+    all_or_nothing: Option<bool>,
+) -> Result<ApiResponse<BulkResult>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let row_ids = rows
+        .iter()
+        .map(|row| Some(row.id.clone()).filter(|id| !id.trim().is_empty()))
+        .collect();
+    match run_bulk(&app_dir, &rows, row_ids, all_or_nothing.unwrap_or(false), insert_vehicle) {
+        Ok(result) => Ok(ApiResponse {
+            success: true,
+            data: Some(result),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
@@ -0,0 +1,288 @@
+// 导入必要的依赖
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rusqlite::{Connection, OpenFlags, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::brand_api::load_vehicle_brands;
+use crate::api::garage_api::{is_empty_slot, load_garages_from, parse_vehicle_list, slot_vehicle_id};
+use crate::api::vehicle_api::map_vehicle_overview;
+use crate::database::has_column;
+use crate::models::{
+    AffectedSlot, ApiResponse, BrandChange, CatalogDiff, DictChange, FieldChange, VehicleBrand,
+    VehicleChange, VehicleOverview,
+};
+
+// 打开候选目录：.sql文件在内存数据库中执行，其他文件按SQLite数据库只读打开
+pub(crate) fn open_candidate_catalog(package_path: &str) -> Result<Connection, String> {
+    let path = Path::new(package_path);
+    if !path.exists() {
+        return Err("候选目录文件不存在".to_string());
+    }
+
+    let is_sql = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("sql"))
+        .unwrap_or(false);
+
+    if is_sql {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let conn = Connection::open_in_memory().map_err(|e| e.to_string())?;
+        conn.execute_batch(&content).map_err(|e| e.to_string())?;
+        Ok(conn)
+    } else {
+        Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|e| e.to_string())
+    }
+}
+
+// 读取目录中的载具，兼容没有dlc_id字段的旧版本目录
+pub(crate) fn load_catalog_vehicles(conn: &Connection) -> Result<Vec<VehicleOverview>> {
+    let dlc_column = if has_column(conn, "vehicle_overview", "dlc_id") {
+        "dlc_id"
+    } else {
+        "NULL"
+    };
+    let version_column = if has_column(conn, "vehicle_overview", "version") {
+        "version"
+    } else {
+        "NULL"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks, {}, {} FROM vehicle_overview",
+        dlc_column, version_column
+    ))?;
+    let vehicle_iter = stmt.query_map([], map_vehicle_overview)?;
+    vehicle_iter.collect()
+}
+
+// 读取字典表，表不存在时视为空字典
+pub(crate) fn load_dict(conn: &Connection, table: &str) -> Result<BTreeMap<String, Option<String>>> {
+    if !has_column(conn, table, "dict_key") {
+        return Ok(BTreeMap::new());
+    }
+    let mut stmt = conn.prepare(&format!("SELECT dict_key, dict_value FROM {}", table))?;
+    let dict_iter = stmt.query_map([], |row| {
+        Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?))
+    })?;
+    let mut dict = BTreeMap::new();
+    for entry in dict_iter {
+        let (key, value) = entry?;
+        if let Some(key) = key {
+            dict.insert(key, value);
+        }
+    }
+    Ok(dict)
+}
+
+// 比较字段，不同时记录变化
+fn compare_field<T: ToString + PartialEq>(
+    changes: &mut Vec<FieldChange>,
+    field: &str,
+    old_value: &Option<T>,
+    new_value: &Option<T>,
+) {
+    if old_value != new_value {
+        changes.push(FieldChange {
+            field: field.to_string(),
+            old_value: old_value.as_ref().map(|v| v.to_string()),
+            new_value: new_value.as_ref().map(|v| v.to_string()),
+        });
+    }
+}
+
+fn diff_vehicle(old: &VehicleOverview, new: &VehicleOverview) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    compare_field(&mut changes, "brand_id", &old.brand_id, &new.brand_id);
+    compare_field(&mut changes, "vehicle_name", &old.vehicle_name, &new.vehicle_name);
+    compare_field(&mut changes, "vehicle_name_en", &old.vehicle_name_en, &new.vehicle_name_en);
+    compare_field(&mut changes, "vehicle_type", &old.vehicle_type, &new.vehicle_type);
+    compare_field(&mut changes, "feature", &old.feature, &new.feature);
+    compare_field(&mut changes, "price", &old.price, &new.price);
+    compare_field(&mut changes, "remarks", &old.remarks, &new.remarks);
+    changes
+}
+
+fn diff_brand(old: &VehicleBrand, new: &VehicleBrand) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    compare_field(&mut changes, "brand_name", &old.brand_name, &new.brand_name);
+    compare_field(&mut changes, "brand_name_en", &old.brand_name_en, &new.brand_name_en);
+    compare_field(&mut changes, "remarks", &old.remarks, &new.remarks);
+    changes
+}
+
+fn diff_dict(
+    dict: &str,
+    old: &BTreeMap<String, Option<String>>,
+    new: &BTreeMap<String, Option<String>>,
+    changes: &mut Vec<DictChange>,
+) {
+    for (key, old_value) in old {
+        match new.get(key) {
+            Some(new_value) if new_value == old_value => {}
+            Some(new_value) => changes.push(DictChange {
+                dict: dict.to_string(),
+                dict_key: key.clone(),
+                old_value: old_value.clone(),
+                new_value: new_value.clone(),
+            }),
+            None => changes.push(DictChange {
+                dict: dict.to_string(),
+                dict_key: key.clone(),
+                old_value: old_value.clone(),
+                new_value: None,
+            }),
+        }
+    }
+    for (key, new_value) in new {
+        if !old.contains_key(key) {
+            changes.push(DictChange {
+                dict: dict.to_string(),
+                dict_key: key.clone(),
+                old_value: None,
+                new_value: new_value.clone(),
+            });
+        }
+    }
+}
+
+// 计算两个目录之间的差异（不含车库影响）
+pub(crate) fn compute_catalog_diff(current: &Connection, candidate: &Connection) -> Result<CatalogDiff> {
+    let mut old_vehicles: BTreeMap<String, VehicleOverview> = load_catalog_vehicles(current)?
+        .into_iter()
+        .map(|v| (v.id.clone(), v))
+        .collect();
+    let mut added_vehicles = Vec::new();
+    let mut modified_vehicles = Vec::new();
+    for vehicle in load_catalog_vehicles(candidate)? {
+        match old_vehicles.remove(&vehicle.id) {
+            Some(old) => {
+                let changes = diff_vehicle(&old, &vehicle);
+                if !changes.is_empty() {
+                    modified_vehicles.push(VehicleChange {
+                        vehicle_id: vehicle.id,
+                        vehicle_name: vehicle.vehicle_name.or(old.vehicle_name),
+                        changes,
+                    });
+                }
+            }
+            None => added_vehicles.push(vehicle),
+        }
+    }
+
+    let mut old_brands: BTreeMap<i32, VehicleBrand> = load_vehicle_brands(current)?
+        .into_iter()
+        .map(|b| (b.id, b))
+        .collect();
+    let mut added_brands = Vec::new();
+    let mut modified_brands = Vec::new();
+    for brand in load_vehicle_brands(candidate)? {
+        match old_brands.remove(&brand.id) {
+            Some(old) => {
+                let changes = diff_brand(&old, &brand);
+                if !changes.is_empty() {
+                    modified_brands.push(BrandChange {
+                        brand_id: brand.id,
+                        brand_name_en: brand.brand_name_en.or(old.brand_name_en),
+                        changes,
+                    });
+                }
+            }
+            None => added_brands.push(brand),
+        }
+    }
+
+    let mut dict_changes = Vec::new();
+    for dict in ["feature_type_dict", "vehicle_type_dict"] {
+        diff_dict(dict, &load_dict(current, dict)?, &load_dict(candidate, dict)?, &mut dict_changes);
+    }
+
+    Ok(CatalogDiff {
+        added_vehicles,
+        removed_vehicles: old_vehicles.into_values().collect(),
+        modified_vehicles,
+        added_brands,
+        removed_brands: old_brands.into_values().collect(),
+        modified_brands,
+        dict_changes,
+        affected_slots: Vec::new(),
+    })
+}
+
+// 找出车库中受删除或修改影响的载具
+fn find_affected_slots(user_conn: &Connection, diff: &CatalogDiff) -> Result<Vec<AffectedSlot>> {
+    let mut affected = Vec::new();
+    for garage in load_garages_from(user_conn, "main")? {
+        let slots = match parse_vehicle_list(garage.vehicle_list.as_deref()) {
+            Ok(slots) => slots,
+            Err(_) => continue,
+        };
+        for (slot_index, slot) in slots.iter().enumerate() {
+            if is_empty_slot(slot) {
+                continue;
+            }
+            let vehicle_id = match slot_vehicle_id(slot) {
+                Some(vehicle_id) => vehicle_id,
+                None => continue,
+            };
+            let change_kind = if diff.removed_vehicles.iter().any(|v| v.id == vehicle_id) {
+                "removed"
+            } else if diff.modified_vehicles.iter().any(|v| v.vehicle_id == vehicle_id) {
+                "modified"
+            } else {
+                continue;
+            };
+            affected.push(AffectedSlot {
+                garage_id: garage.id.unwrap_or_default(),
+                garage_name: garage.garage_name.clone(),
+                slot: slot_index,
+                vehicle_id,
+                change_kind: change_kind.to_string(),
+            });
+        }
+    }
+    Ok(affected)
+}
+
+// 比较候选目录与当前目录
+#[command]
+pub fn diff_catalog(app: AppHandle, package_path: String) -> Result<ApiResponse<CatalogDiff>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let candidate = match open_candidate_catalog(&package_path) {
+        Ok(conn) => conn,
+        Err(e) => {
+            return Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        }
+    };
+
+    let result = Connection::open(app_dir.join("gtavm_common.db"))
+        .and_then(|current| compute_catalog_diff(&current, &candidate))
+        .and_then(|mut diff| {
+            let user_conn = Connection::open(app_dir.join("gtavm_user.db"))?;
+            diff.affected_slots = find_affected_slots(&user_conn, &diff)?;
+            Ok(diff)
+        });
+
+    match result {
+        Ok(diff) => Ok(ApiResponse {
+            success: true,
+            data: Some(diff),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
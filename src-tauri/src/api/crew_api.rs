@@ -0,0 +1,399 @@
+// 导入必要的依赖
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::garage_api::{load_garages, load_garages_from, owned_vehicle_ids};
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::database::has_column;
+use crate::models::{
+    ApiResponse, CrewComparison, CrewMember, CrewOwnership, CrewVehicle, GarageOverview, UnreadableCrewMember,
+    VehicleOverview,
+};
+
+// 车队成员车库数据副本的存放目录
+fn crew_vault_dir(app_dir: &Path) -> PathBuf {
+    app_dir.join("crew_vaults")
+}
+
+// 只读打开成员的车库数据
+fn open_crew_vault(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+}
+
+// 读取车库数据文件中的车库
+// 文件包含角色时只读取导出时的当前角色，与游戏中参加活动的角色一致
+fn load_vault_garages(conn: &Connection) -> Result<Vec<GarageOverview>, String> {
+    if !has_column(conn, "garage_overview", "vehicle_list") {
+        return Err("不是有效的车库数据文件".to_string());
+    }
+    let garages = if !has_column(conn, "garage_overview", "version") {
+        load_legacy_garages(conn)
+    } else if has_column(conn, "garage_overview", "profile_id") && has_column(conn, "profile", "is_active") {
+        load_garages(conn)
+    } else {
+        load_garages_from(conn, "main")
+    };
+    garages.map_err(|e| e.to_string())
+}
+
+// 旧版本导出的文件缺少version字段，只读取对比需要的字段
+fn load_legacy_garages(conn: &Connection) -> Result<Vec<GarageOverview>> {
+    let mut stmt = conn.prepare("SELECT id, garage_name, num, vehicle_list FROM garage_overview")?;
+    let garage_iter = stmt.query_map([], |row| {
+        Ok(GarageOverview {
+            id: Some(row.get(0)?),
+            garage_name: row.get(1)?,
+            garage_name_en: None,
+            num: row.get(2)?,
+            vehicle_list: row.get(3)?,
+            remarks: None,
+            garage_order: None,
+            garage_type: None,
+            version: None,
+        })
+    })?;
+    garage_iter.collect()
+}
+
+// 读取所有车队成员
+fn load_crew_members(app_dir: &Path, conn: &Connection) -> Result<Vec<CrewMember>> {
+    let mut stmt = conn.prepare("SELECT id, member_name, file_name, imported_at FROM crew_member ORDER BY id ASC")?;
+    let member_iter = stmt.query_map([], |row| {
+        Ok(CrewMember {
+            id: row.get(0)?,
+            member_name: row.get(1)?,
+            file_name: row.get(2)?,
+            imported_at: row.get(3)?,
+            garage_count: 0,
+            vehicle_count: 0,
+        })
+    })?;
+    let mut members = member_iter.collect::<Result<Vec<_>>>()?;
+    // 副本文件丢失时数量为0
+    for member in &mut members {
+        let path = crew_vault_dir(app_dir).join(&member.file_name);
+        if let Ok(garages) = open_crew_vault(&path).map_err(|e| e.to_string()).and_then(|c| load_vault_garages(&c)) {
+            member.garage_count = garages.len();
+            member.vehicle_count = owned_vehicle_ids(&garages).len();
+        }
+    }
+    Ok(members)
+}
+
+// 导出当前车库数据，供车队其他成员导入
+#[command]
+pub fn export_user_vault(app: AppHandle, output_path: String) -> Result<ApiResponse<String>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    // VACUUM INTO生成一致的快照，目标文件必须不存在
+    let result = if Path::new(&output_path).exists() {
+        Err("导出文件已存在".to_string())
+    } else {
+        Connection::open(app_dir.join("gtavm_user.db"))
+            .and_then(|conn| conn.execute("VACUUM INTO ?1", params![output_path]))
+            .map(|_| output_path)
+            .map_err(|e| e.to_string())
+    };
+
+    match result {
+        Ok(path) => Ok(ApiResponse {
+            success: true,
+            data: Some(path),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 导入其他成员导出的车库数据，作为只读的外部数据保存
+#[command]
+pub fn import_crew_vault(app: AppHandle, path: String, member_name: String) -> Result<ApiResponse<CrewMember>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_import_crew_vault(&app_dir, Path::new(&path), &member_name) {
+        Ok(member) => Ok(ApiResponse {
+            success: true,
+            data: Some(member),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_import_crew_vault(app_dir: &Path, source: &Path, member_name: &str) -> Result<CrewMember, String> {
+    if member_name.trim().is_empty() {
+        return Err("成员名称不能为空".to_string());
+    }
+    // 先校验文件内容，避免保存无效的副本
+    let source_conn = open_crew_vault(source).map_err(|e| e.to_string())?;
+    load_vault_garages(&source_conn)?;
+    drop(source_conn);
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let vault_dir = crew_vault_dir(app_dir);
+    fs::create_dir_all(&vault_dir).map_err(|e| e.to_string())?;
+
+    let mut conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO crew_member (member_name, file_name, imported_at) VALUES (?1, '', date('now'))",
+        params![member_name.trim()],
+    )
+    .map_err(|e| e.to_string())?;
+    let id = tx.last_insert_rowid() as i32;
+    let file_name = format!("{}_{}.db", id, timestamp);
+    tx.execute("UPDATE crew_member SET file_name = ?1 WHERE id = ?2", params![file_name, id])
+        .map_err(|e| e.to_string())?;
+    fs::copy(source, vault_dir.join(&file_name)).map_err(|e| format!("复制车库数据失败: {}", e))?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    load_crew_members(app_dir, &conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|member| member.id == id)
+        .ok_or_else(|| "成员导入失败".to_string())
+}
+
+// 获取所有车队成员
+#[command]
+pub fn get_crew_members(app: AppHandle) -> Result<ApiResponse<Vec<CrewMember>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match Connection::open(app_dir.join("gtavm_user.db")).and_then(|conn| load_crew_members(&app_dir, &conn)) {
+        Ok(members) => Ok(ApiResponse {
+            success: true,
+            data: Some(members),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 查看车队成员的车库（只读）
+#[command]
+pub fn get_crew_member_garages(app: AppHandle, id: i32) -> Result<ApiResponse<Vec<GarageOverview>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_user.db"))
+        .and_then(|conn| {
+            conn.query_row("SELECT file_name FROM crew_member WHERE id = ?1", params![id], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()
+        })
+        .map_err(|e| e.to_string())
+        .and_then(|file_name| file_name.ok_or_else(|| "成员不存在".to_string()))
+        .and_then(|file_name| {
+            open_crew_vault(&crew_vault_dir(&app_dir).join(file_name)).map_err(|e| e.to_string())
+        })
+        .and_then(|conn| load_vault_garages(&conn));
+
+    match result {
+        Ok(garages) => Ok(ApiResponse {
+            success: true,
+            data: Some(garages),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 删除车队成员及其数据副本
+#[command]
+pub fn delete_crew_member(app: AppHandle, id: i32) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_user.db"))
+        .and_then(|conn| {
+            let file_name: Option<String> = conn
+                .query_row("SELECT file_name FROM crew_member WHERE id = ?1", params![id], |row| row.get(0))
+                .optional()?;
+            conn.execute("DELETE FROM crew_member WHERE id = ?1", params![id])?;
+            Ok(file_name)
+        })
+        .map_err(|e| e.to_string())
+        .and_then(|file_name| match file_name {
+            Some(file_name) => {
+                let _ = fs::remove_file(crew_vault_dir(&app_dir).join(file_name));
+                Ok(())
+            }
+            None => Err("成员不存在".to_string()),
+        });
+
+    match result {
+        Ok(_) => Ok(ApiResponse {
+            success: true,
+            data: None,
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 当前角色的名称，对比时代表自己
+pub(crate) fn active_profile_name(conn: &Connection) -> Result<String, String> {
+    conn.query_row("SELECT profile_name FROM profile WHERE is_active = 1 ORDER BY id LIMIT 1", [], |row| {
+        row.get(0)
+    })
+    .optional()
+    .map(|name| name.unwrap_or_else(|| "我".to_string()))
+    .map_err(|e| e.to_string())
+}
+
+// 已读取的车队成员车库，unreadable为副本丢失或损坏而跳过的成员
+pub(crate) struct CrewGarages {
+    pub members: Vec<(CrewMember, Vec<GarageOverview>)>,
+    pub unreadable: Vec<UnreadableCrewMember>,
+}
+
+// 读取车队成员的车库，member_ids为空时读取所有成员
+// 单个成员的副本无法读取时跳过该成员并记录原因，不影响其他成员
+pub(crate) fn load_crew_garages(
+    app_dir: &Path,
+    conn: &Connection,
+    member_ids: Option<&[i32]>,
+) -> Result<CrewGarages, String> {
+    let mut crew = CrewGarages {
+        members: Vec::new(),
+        unreadable: Vec::new(),
+    };
+    for member in load_crew_members(app_dir, conn).map_err(|e| e.to_string())? {
+        if let Some(ids) = member_ids {
+            if !ids.contains(&member.id) {
+                continue;
+            }
+        }
+        let garages = open_crew_vault(&crew_vault_dir(app_dir).join(&member.file_name))
+            .map_err(|e| e.to_string())
+            .and_then(|vault| load_vault_garages(&vault));
+        match garages {
+            Ok(garages) => crew.members.push((member, garages)),
+            Err(e) => crew.unreadable.push(UnreadableCrewMember {
+                member_id: member.id,
+                member_name: member.member_name,
+                error: e,
+            }),
+        }
+    }
+    Ok(crew)
+}
+
+fn crew_vehicle(vehicle: &VehicleOverview) -> CrewVehicle {
+    CrewVehicle {
+        vehicle_id: vehicle.id.clone(),
+        vehicle_name: vehicle.vehicle_name.clone(),
+        vehicle_name_en: vehicle.vehicle_name_en.clone(),
+        price: vehicle.price,
+    }
+}
+
+// 对比自己与车队成员拥有的载具，以目录中的载具ID为准
+// member_ids为空时对比所有成员
+#[command]
+pub fn compare_crew_vaults(
+    app: AppHandle,
+    member_ids: Option<Vec<i32>>,
+) -> Result<ApiResponse<CrewComparison>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_compare_crew_vaults(&app_dir, member_ids) {
+        Ok(comparison) => Ok(ApiResponse {
+            success: true,
+            data: Some(comparison),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_compare_crew_vaults(app_dir: &Path, member_ids: Option<Vec<i32>>) -> Result<CrewComparison, String> {
+    let user_conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+
+    let mut owners: Vec<(String, HashSet<String>)> = vec![(
+        active_profile_name(&user_conn)?,
+        owned_vehicle_ids(&load_garages(&user_conn).map_err(|e| e.to_string())?),
+    )];
+    let crew = load_crew_garages(app_dir, &user_conn, member_ids.as_deref())?;
+    for (member, garages) in crew.members {
+        owners.push((member.member_name, owned_vehicle_ids(&garages)));
+    }
+
+    let common_conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    let vehicles = load_vehicle_overviews(&common_conn).map_err(|e| e.to_string())?;
+
+    let mut partially_owned = Vec::new();
+    let mut crew_missing = Vec::new();
+    // 按载具ID排序，保证结果稳定
+    let mut ordered: Vec<&VehicleOverview> = vehicles.iter().collect();
+    ordered.sort_by(|a, b| a.id.cmp(&b.id));
+    for vehicle in ordered {
+        let (owning, lacking): (Vec<_>, Vec<_>) = owners.iter().partition(|(_, owned)| owned.contains(&vehicle.id));
+        if owning.is_empty() {
+            crew_missing.push(crew_vehicle(vehicle));
+        } else if !lacking.is_empty() {
+            partially_owned.push(CrewOwnership {
+                vehicle: crew_vehicle(vehicle),
+                owners: owning.into_iter().map(|(name, _)| name.clone()).collect(),
+                lacking: lacking.into_iter().map(|(name, _)| name.clone()).collect(),
+            });
+        }
+    }
+
+    Ok(CrewComparison {
+        members: owners.into_iter().map(|(name, _)| name).collect(),
+        partially_owned,
+        crew_missing,
+        unreadable_members: crew.unreadable,
+    })
+}
@@ -66,3 +66,16 @@ pub fn get_data_info(app: AppHandle) -> Result<ApiResponse<DataInfo>, String> {
         }),
     }
 }
+
+// 读取数据信息，没有记录时返回None
+pub(crate) fn load_data_info(conn: &Connection) -> Result<Option<DataInfo>> {
+    let mut stmt = conn.prepare("SELECT dlc_name, dlc_name_en, update_time FROM data_info LIMIT 1")?;
+    let mut data_info_iter = stmt.query_map([], |row| {
+        Ok(DataInfo {
+            dlc_name: row.get(0)?,
+            dlc_name_en: row.get(1)?,
+            update_time: row.get(2)?,
+        })
+    })?;
+    data_info_iter.next().transpose()
+}
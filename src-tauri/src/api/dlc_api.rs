@@ -0,0 +1,253 @@
+// 导入必要的依赖
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::vehicle_api::map_vehicle_overview;
+use crate::models::{ApiResponse, DlcRelease, VehicleOverview};
+
+// 获取所有DLC发布记录（按发布时间排序）
+#[command]
+pub fn get_dlc_releases(app: AppHandle) -> Result<ApiResponse<Vec<DlcRelease>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    let result = Connection::open(&db_path).and_then(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT r.id, r.dlc_name, r.dlc_name_en, r.release_date, r.catalog_version,
+                    (SELECT COUNT(*) FROM vehicle_overview v WHERE v.dlc_id = r.id)
+             FROM dlc_release r
+             ORDER BY r.release_date ASC, r.catalog_version ASC",
+        )?;
+        let release_iter = stmt.query_map([], |row| {
+            Ok(DlcRelease {
+                id: Some(row.get(0)?),
+                dlc_name: row.get(1)?,
+                dlc_name_en: row.get(2)?,
+                release_date: row.get(3)?,
+                catalog_version: row.get(4)?,
+                vehicle_count: Some(row.get(5)?),
+            })
+        })?;
+        release_iter.collect::<Result<Vec<_>>>()
+    });
+
+    match result {
+        Ok(releases) => Ok(ApiResponse {
+            success: true,
+            data: Some(releases),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 添加DLC发布记录（用于补录历史DLC）
+#[command]
+pub fn add_dlc_release(
+    app: AppHandle,
+    release: DlcRelease,
+) -> Result<ApiResponse<DlcRelease>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    match Connection::open(&db_path) {
+        Ok(conn) => match conn.execute(
+            "INSERT INTO dlc_release (dlc_name, dlc_name_en, release_date, catalog_version)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                release.dlc_name,
+                release.dlc_name_en,
+                release.release_date,
+                release.catalog_version
+            ],
+        ) {
+            Ok(_) => Ok(ApiResponse {
+                success: true,
+                data: Some(DlcRelease {
+                    id: Some(conn.last_insert_rowid() as i32),
+                    vehicle_count: Some(0),
+                    ..release
+                }),
+                error: None,
+            }),
+            Err(e) => Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            }),
+        },
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 设置载具所属的DLC
+#[command]
+pub fn set_vehicle_dlc(
+    app: AppHandle,
+    vehicle_ids: Vec<String>,
+    dlc_id: i32,
+) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    match Connection::open(&db_path) {
+        Ok(mut conn) => {
+            match conn.transaction() {
+                Ok(tx) => {
+                    // 检查DLC是否存在
+                    let dlc_exists: i64 = tx
+                        .query_row(
+                            "SELECT COUNT(*) FROM dlc_release WHERE id = ?1",
+                            params![dlc_id],
+                            |row| row.get(0),
+                        )
+                        .unwrap_or(0);
+                    if dlc_exists == 0 {
+                        return Ok(ApiResponse {
+                            success: false,
+                            data: None,
+                            error: Some("指定的DLC不存在".to_string()),
+                        });
+                    }
+
+                    // 载具必须存在，否则vehicle_dlc中会留下不对应任何载具的记录
+                    let mut unknown_ids = Vec::new();
+                    for vehicle_id in &vehicle_ids {
+                        match tx
+                            .query_row("SELECT 1 FROM vehicle_overview WHERE id = ?1", params![vehicle_id], |_| Ok(()))
+                            .optional()
+                        {
+                            Ok(Some(_)) => {}
+                            Ok(None) => unknown_ids.push(vehicle_id.as_str()),
+                            Err(e) => {
+                                return Ok(ApiResponse {
+                                    success: false,
+                                    data: None,
+                                    error: Some(e.to_string()),
+                                })
+                            }
+                        }
+                    }
+                    if !unknown_ids.is_empty() {
+                        return Ok(ApiResponse {
+                            success: false,
+                            data: None,
+                            error: Some(format!("载具不存在: {}", unknown_ids.join(", "))),
+                        });
+                    }
+
+                    for vehicle_id in &vehicle_ids {
+                        // vehicle_dlc保存对应关系，确保目录更新后仍然有效
+                        let result = tx
+                            .execute(
+                                "UPDATE vehicle_overview SET dlc_id = ?1, version = version + 1 WHERE id = ?2",
+                                params![dlc_id, vehicle_id],
+                            )
+                            .and_then(|_| {
+                                tx.execute(
+                                    "INSERT OR REPLACE INTO vehicle_dlc (vehicle_id, dlc_id) VALUES (?1, ?2)",
+                                    params![vehicle_id, dlc_id],
+                                )
+                            });
+                        if let Err(e) = result {
+                            // 回滚事务
+                            let _ = tx.rollback();
+                            return Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e.to_string()),
+                            });
+                        }
+                    }
+
+                    match tx.commit() {
+                        Ok(_) => Ok(ApiResponse {
+                            success: true,
+                            data: None,
+                            error: None,
+                        }),
+                        Err(e) => Ok(ApiResponse {
+                            success: false,
+                            data: None,
+                            error: Some(e.to_string()),
+                        }),
+                    }
+                }
+                Err(e) => Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 按DLC获取载具概览，include_later为true时同时返回之后所有DLC引入的载具
+#[command]
+pub fn get_vehicle_overviews_by_dlc(
+    app: AppHandle,
+    dlc_id: i32,
+    include_later: Option<bool>,
+) -> Result<ApiResponse<Vec<VehicleOverview>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    let result = Connection::open(&db_path).and_then(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT v.id, v.brand_id, v.vehicle_name, v.vehicle_name_en, v.vehicle_type, v.feature, v.price, v.remarks, v.dlc_id, v.version
+             FROM vehicle_overview v
+             JOIN dlc_release r ON r.id = v.dlc_id
+             JOIN dlc_release s ON s.id = ?1
+             WHERE r.id = s.id
+                OR (?2 AND (COALESCE(r.release_date, '') > COALESCE(s.release_date, '')
+                    OR (COALESCE(r.release_date, '') = COALESCE(s.release_date, '')
+                        AND COALESCE(r.catalog_version, 0) > COALESCE(s.catalog_version, 0))))
+             ORDER BY r.release_date ASC, r.catalog_version ASC, v.id ASC",
+        )?;
+        let vehicle_iter = stmt.query_map(
+            params![dlc_id, include_later.unwrap_or(false)],
+            map_vehicle_overview,
+        )?;
+        vehicle_iter.collect::<Result<Vec<_>>>()
+    });
+
+    match result {
+        Ok(vehicles) => Ok(ApiResponse {
+            success: true,
+            data: Some(vehicles),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
@@ -0,0 +1,123 @@
+// 导入必要的依赖
+use std::collections::{BTreeMap, HashMap};
+
+use rusqlite::Connection;
+use serde_json::Value;
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::garage_api::{is_empty_slot, load_garages, parse_vehicle_list, slot_vehicle_id};
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::models::{ApiResponse, DuplicateLocation, DuplicateVehicle, VehicleOverview};
+
+// 判断车位是否被标记为有意重复购买（通过update_slot_instance设置）
+fn is_intentional_duplicate(slot: &Value) -> bool {
+    slot.get("intentional_duplicate")
+        .and_then(|flag| flag.as_bool())
+        .unwrap_or(false)
+}
+
+// 查找占用多个车位的载具
+#[command]
+pub fn find_duplicates(
+    app: AppHandle,
+    include_intentional: Option<bool>,
+) -> Result<ApiResponse<Vec<DuplicateVehicle>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let user_db_path = app_dir.join("gtavm_user.db");
+    let common_db_path = app_dir.join("gtavm_common.db");
+
+    let garages = match Connection::open(&user_db_path).and_then(|conn| load_garages(&conn)) {
+        Ok(garages) => garages,
+        Err(e) => {
+            return Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            })
+        }
+    };
+
+    let vehicles: HashMap<String, VehicleOverview> =
+        match Connection::open(&common_db_path).and_then(|conn| load_vehicle_overviews(&conn)) {
+            Ok(vehicles) => vehicles.into_iter().map(|v| (v.id.clone(), v)).collect(),
+            Err(e) => {
+                return Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+
+    // 按载具ID汇总所有车位
+    let mut locations_by_vehicle: BTreeMap<String, Vec<DuplicateLocation>> = BTreeMap::new();
+    for garage in &garages {
+        // 跳过无法解析的车库
+        let slots = match parse_vehicle_list(garage.vehicle_list.as_deref()) {
+            Ok(slots) => slots,
+            Err(_) => continue,
+        };
+        for (slot_index, slot) in slots.iter().enumerate() {
+            if is_empty_slot(slot) {
+                continue;
+            }
+            if let Some(vehicle_id) = slot_vehicle_id(slot) {
+                locations_by_vehicle
+                    .entry(vehicle_id)
+                    .or_default()
+                    .push(DuplicateLocation {
+                        garage_id: garage.id.unwrap_or_default(),
+                        garage_name: garage.garage_name.clone(),
+                        slot: slot_index,
+                        intentional: is_intentional_duplicate(slot),
+                        remarks: slot
+                            .get("remarks")
+                            .and_then(|remarks| remarks.as_str())
+                            .filter(|remarks| !remarks.is_empty())
+                            .map(|remarks| remarks.to_string()),
+                    });
+            }
+        }
+    }
+
+    let include_intentional = include_intentional.unwrap_or(true);
+    let mut duplicates = Vec::new();
+    for (vehicle_id, locations) in locations_by_vehicle {
+        if locations.len() < 2 {
+            continue;
+        }
+        let count = locations.len();
+        let intentional_count = locations.iter().filter(|l| l.intentional).count();
+        // 除一辆外其余均为有意重复时，视为已确认的重复
+        if !include_intentional && count - intentional_count <= 1 {
+            continue;
+        }
+
+        let vehicle = vehicles.get(&vehicle_id);
+        let price = vehicle.and_then(|v| v.price);
+        let unit_price = price.unwrap_or(0) as i64;
+        let redundant_count = (count - intentional_count).saturating_sub(1);
+
+        duplicates.push(DuplicateVehicle {
+            vehicle_name: vehicle.and_then(|v| v.vehicle_name.clone()),
+            vehicle_name_en: vehicle.and_then(|v| v.vehicle_name_en.clone()),
+            vehicle_id,
+            price,
+            count,
+            intentional_count,
+            locations,
+            total_value: unit_price * count as i64,
+            redundant_value: unit_price * redundant_count as i64,
+        });
+    }
+
+    Ok(ApiResponse {
+        success: true,
+        data: Some(duplicates),
+        error: None,
+    })
+}
@@ -0,0 +1,291 @@
+// 导入必要的依赖
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::{params, Connection, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::crew_api::{active_profile_name, load_crew_garages};
+use crate::api::garage_api::{load_garages, parse_vehicle_list, slot_vehicle_id};
+use crate::api::vehicle_api::{load_vehicle_overviews, split_features};
+use crate::models::{ApiResponse, EventPreset, GarageOverview, OwnedVehicleCriteria, OwnedVehicleMatch, OwnedVehicleSearch, VehicleOverview};
+
+// 读取字典表，返回dict_key到dict_value的映射
+fn load_dict_values(conn: &Connection, table: &str) -> Result<HashMap<String, String>> {
+    let mut stmt = conn.prepare(&format!("SELECT dict_key, dict_value FROM {}", table))?;
+    let dict_iter = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+            row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+        ))
+    })?;
+    dict_iter.collect()
+}
+
+// 条件与字典键或字典值相同即视为匹配，不区分大小写
+fn dict_matches(wanted: &str, key: &str, dict: &HashMap<String, String>) -> bool {
+    let wanted = wanted.trim();
+    wanted.eq_ignore_ascii_case(key)
+        || dict
+            .get(key)
+            .map(|value| wanted.eq_ignore_ascii_case(value))
+            .unwrap_or(false)
+}
+
+// 判断载具是否满足活动要求
+fn vehicle_matches(
+    vehicle: &VehicleOverview,
+    criteria: &OwnedVehicleCriteria,
+    vehicle_types: &HashMap<String, String>,
+    features: &HashMap<String, String>,
+) -> bool {
+    if let Some(brand_ids) = criteria.brand_ids.as_ref().filter(|ids| !ids.is_empty()) {
+        if !vehicle.brand_id.map(|id| brand_ids.contains(&id)).unwrap_or(false) {
+            return false;
+        }
+    }
+    if let Some(wanted_types) = criteria.vehicle_types.as_ref().filter(|types| !types.is_empty()) {
+        let vehicle_type = vehicle.vehicle_type.as_deref().unwrap_or("");
+        if vehicle_type.is_empty() || !wanted_types.iter().any(|wanted| dict_matches(wanted, vehicle_type, vehicle_types)) {
+            return false;
+        }
+    }
+    if let Some(wanted_features) = criteria.features.as_ref() {
+        let vehicle_features = split_features(vehicle.feature.as_deref());
+        if !wanted_features
+            .iter()
+            .all(|wanted| vehicle_features.iter().any(|key| dict_matches(wanted, key, features)))
+        {
+            return false;
+        }
+    }
+    true
+}
+
+// 在车库中查找符合要求的载具，按车库顺序和车位顺序返回
+fn collect_matches(
+    owner: &str,
+    crew_member_id: Option<i32>,
+    garages: &[GarageOverview],
+    matching: &HashMap<&str, &VehicleOverview>,
+    matches: &mut Vec<OwnedVehicleMatch>,
+) {
+    for garage in garages {
+        // 跳过无法解析的车库
+        let slots = match parse_vehicle_list(garage.vehicle_list.as_deref()) {
+            Ok(slots) => slots,
+            Err(_) => continue,
+        };
+        for (index, slot) in slots.iter().enumerate() {
+            let vehicle = match slot_vehicle_id(slot).and_then(|id| matching.get(id.as_str()).copied()) {
+                Some(vehicle) => vehicle,
+                None => continue,
+            };
+            matches.push(OwnedVehicleMatch {
+                owner: owner.to_string(),
+                crew_member_id,
+                garage_id: garage.id.unwrap_or_default(),
+                garage_name: garage.garage_name.clone(),
+                slot: index,
+                vehicle_id: vehicle.id.clone(),
+                vehicle_name: vehicle.vehicle_name.clone(),
+                vehicle_name_en: vehicle.vehicle_name_en.clone(),
+                vehicle_type: vehicle.vehicle_type.clone(),
+                feature: vehicle.feature.clone(),
+                brand_id: vehicle.brand_id,
+            });
+        }
+    }
+}
+
+fn run_find_owned_vehicles(app_dir: &Path, criteria: &OwnedVehicleCriteria) -> Result<OwnedVehicleSearch, String> {
+    let common_conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    let vehicles = load_vehicle_overviews(&common_conn).map_err(|e| e.to_string())?;
+    let vehicle_types = load_dict_values(&common_conn, "vehicle_type_dict").map_err(|e| e.to_string())?;
+    let features = load_dict_values(&common_conn, "feature_type_dict").map_err(|e| e.to_string())?;
+    let matching: HashMap<&str, &VehicleOverview> = vehicles
+        .iter()
+        .filter(|vehicle| vehicle_matches(vehicle, criteria, &vehicle_types, &features))
+        .map(|vehicle| (vehicle.id.as_str(), vehicle))
+        .collect();
+
+    let user_conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let garages = load_garages(&user_conn).map_err(|e| e.to_string())?;
+    let mut matches = Vec::new();
+    collect_matches(&active_profile_name(&user_conn)?, None, &garages, &matching, &mut matches);
+
+    let mut unreadable_members = Vec::new();
+    if criteria.include_crew.unwrap_or(false) {
+        let crew = load_crew_garages(app_dir, &user_conn, None)?;
+        for (member, garages) in crew.members {
+            collect_matches(&member.member_name, Some(member.id), &garages, &matching, &mut matches);
+        }
+        unreadable_members = crew.unreadable;
+    }
+    Ok(OwnedVehicleSearch {
+        matches,
+        unreadable_members,
+    })
+}
+
+// 按载具类型、特性和品牌查找已拥有的载具，返回取车的车库和车位
+// include_crew为true时同时查找车队成员的车库
+#[command]
+pub fn find_owned_vehicles(
+    app: AppHandle,
+    criteria: OwnedVehicleCriteria,
+) -> Result<ApiResponse<OwnedVehicleSearch>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_find_owned_vehicles(&app_dir, &criteria) {
+        Ok(search) => Ok(ApiResponse {
+            success: true,
+            data: Some(search),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 读取所有活动要求预设
+fn load_event_presets(conn: &Connection) -> Result<Vec<EventPreset>, String> {
+    let mut stmt = conn
+        .prepare("SELECT id, preset_name, criteria, remarks FROM event_preset ORDER BY id ASC")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.into_iter()
+        .map(|(id, preset_name, criteria, remarks)| {
+            let criteria = serde_json::from_str(&criteria)
+                .map_err(|e| format!("预设{}的条件解析失败: {}", preset_name, e))?;
+            Ok(EventPreset {
+                id: Some(id),
+                preset_name,
+                criteria,
+                remarks,
+            })
+        })
+        .collect()
+}
+
+// 获取所有活动要求预设
+#[command]
+pub fn get_event_presets(app: AppHandle) -> Result<ApiResponse<Vec<EventPreset>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match Connection::open(app_dir.join("gtavm_user.db"))
+        .map_err(|e| e.to_string())
+        .and_then(|conn| load_event_presets(&conn))
+    {
+        Ok(presets) => Ok(ApiResponse {
+            success: true,
+            data: Some(presets),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 保存活动要求预设，id为空时新增，否则更新，返回预设ID
+#[command]
+pub fn save_event_preset(app: AppHandle, preset: EventPreset) -> Result<ApiResponse<i32>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = if preset.preset_name.trim().is_empty() {
+        Err("预设名称不能为空".to_string())
+    } else {
+        serde_json::to_string(&preset.criteria)
+            .map_err(|e| e.to_string())
+            .and_then(|criteria| {
+                let conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+                match preset.id {
+                    Some(id) => {
+                        let changed = conn
+                            .execute(
+                                "UPDATE event_preset SET preset_name = ?1, criteria = ?2, remarks = ?3 WHERE id = ?4",
+                                params![preset.preset_name.trim(), criteria, preset.remarks, id],
+                            )
+                            .map_err(|e| e.to_string())?;
+                        if changed == 0 {
+                            return Err("预设不存在".to_string());
+                        }
+                        Ok(id)
+                    }
+                    None => {
+                        conn.execute(
+                            "INSERT INTO event_preset (preset_name, criteria, remarks) VALUES (?1, ?2, ?3)",
+                            params![preset.preset_name.trim(), criteria, preset.remarks],
+                        )
+                        .map_err(|e| e.to_string())?;
+                        Ok(conn.last_insert_rowid() as i32)
+                    }
+                }
+            })
+    };
+
+    match result {
+        Ok(id) => Ok(ApiResponse {
+            success: true,
+            data: Some(id),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 删除活动要求预设
+#[command]
+pub fn delete_event_preset(app: AppHandle, id: i32) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match Connection::open(app_dir.join("gtavm_user.db"))
+        .and_then(|conn| conn.execute("DELETE FROM event_preset WHERE id = ?1", params![id]))
+    {
+        Ok(_) => Ok(ApiResponse {
+            success: true,
+            data: None,
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
@@ -1,9 +1,19 @@
 // 导入必要的依赖
-use rusqlite::{params, Connection, Result};
+use std::collections::HashSet;
+use std::path::Path;
+
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection, Result};
+use serde_json::{json, Map, Value};
 use tauri::{command, AppHandle, Manager};
 
 // 导入数据模型
-use crate::models::{ApiResponse, GarageOverview};
+use crate::api::vehicle_api::{check_version, PatchOutcome};
+use crate::database::ACTIVE_PROFILE;
+use crate::models::{
+    AffectedSlot, ApiResponse, GarageOverview, GaragePatch, OverflowPolicy, ResizeGarageResult, SlotMove, VehicleBrand,
+    VehicleOverview,
+};
 
 // 获取所有车库概览
 #[command]
@@ -15,9 +25,11 @@ pub fn get_garage_overviews(app: AppHandle) -> Result<ApiResponse<Vec<GarageOver
     let db_path = app_dir.join("gtavm_user.db");
 
     match Connection::open(&db_path) {
-        Ok(conn) => match conn.prepare(
-            "SELECT id, garage_name, garage_name_en, num, vehicle_list, remarks, garage_order, garage_type FROM garage_overview ORDER BY garage_order ASC",
-        ) {
+        // 只返回当前角色的车库
+        Ok(conn) => match conn.prepare(&format!(
+            "SELECT id, garage_name, garage_name_en, num, vehicle_list, remarks, garage_order, garage_type, version FROM garage_overview WHERE profile_id = {} ORDER BY garage_order ASC",
+            ACTIVE_PROFILE
+        )) {
             Ok(mut stmt) => {
                 match stmt.query_map([], |row| {
                     Ok(GarageOverview {
@@ -29,6 +41,7 @@ pub fn get_garage_overviews(app: AppHandle) -> Result<ApiResponse<Vec<GarageOver
                         remarks: row.get(5)?,
                         garage_order: row.get(6)?,
                         garage_type: row.get(7)?,
+                        version: row.get(8)?,
                     })
                 }) {
                     Ok(garage_iter) => match garage_iter.collect::<Result<_>>() {
@@ -83,7 +96,7 @@ pub fn add_garage_overview(
                     // 插入新记录，不指定ID，让SQLite自动生成
                     // 获取当前最大garage_order值，用于设置新车库的默认garage_order
                     let max_order: Option<i32> = tx.query_row(
-                        "SELECT MAX(garage_order) FROM garage_overview",
+                        &format!("SELECT MAX(garage_order) FROM garage_overview WHERE profile_id = {}", ACTIVE_PROFILE),
                         [],
                         |row| row.get(0)
                     ).unwrap_or(None);
@@ -91,9 +104,13 @@ pub fn add_garage_overview(
                     // 新车库的默认garage_order为当前最大garage_order值+1，若没有车库则为1
                     let new_order = max_order.map(|o| o + 1).unwrap_or(1);
                     
+                    // 新车库归属当前角色
                     match tx.execute(
-                        "INSERT INTO garage_overview (garage_name, garage_name_en, num, vehicle_list, remarks, garage_order, garage_type) 
-                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        &format!(
+                            "INSERT INTO garage_overview (garage_name, garage_name_en, num, vehicle_list, remarks, garage_order, garage_type, profile_id) 
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, {})",
+                            ACTIVE_PROFILE
+                        ),
                         params![
                             garage.garage_name,
                             garage.garage_name_en,
@@ -121,7 +138,8 @@ pub fn add_garage_overview(
                                 vehicle_list: garage.vehicle_list,
                                 remarks: garage.remarks,
                                 garage_order: Some(garage.garage_order.unwrap_or(new_order)),
-                                garage_type: garage.garage_type
+                                garage_type: garage.garage_type,
+                                version: Some(0),
                             };
                                     
                                     Ok(ApiResponse {
@@ -153,7 +171,7 @@ pub fn add_garage_overview(
 #[command]
 pub fn update_garage_overview(
     app: AppHandle,
-    garage: GarageOverview,
+    mut garage: GarageOverview,
 ) -> Result<ApiResponse<GarageOverview>, String> {
     // 检查ID是否存在
     let garage_id = match garage.id {
@@ -171,8 +189,9 @@ pub fn update_garage_overview(
         Ok(mut conn) => {
             match conn.transaction() {
                 Ok(tx) => {
-                    match tx.execute(
-                        "UPDATE garage_overview SET garage_name = ?1, garage_name_en = ?2, num = ?3, vehicle_list = ?4, remarks = ?5, garage_order = ?6, garage_type = ?7 WHERE id = ?8",
+                    // 返回递增后的版本号，客户端可直接用于后续的部分更新
+                    match tx.query_row(
+                        "UPDATE garage_overview SET garage_name = ?1, garage_name_en = ?2, num = ?3, vehicle_list = ?4, remarks = ?5, garage_order = ?6, garage_type = ?7, version = version + 1 WHERE id = ?8 RETURNING version",
                         params![
                             garage.garage_name,
                             garage.garage_name_en,
@@ -183,8 +202,10 @@ pub fn update_garage_overview(
                             garage.garage_type,
                             garage_id
                         ],
+                        |row| row.get(0),
                     ) {
-                        Ok(_) => {
+                        Ok(version) => {
+                            garage.version = version;
                             match tx.commit() {
                                 Ok(_) => {
                                     Ok(ApiResponse {
@@ -227,6 +248,109 @@ pub fn update_garage_overview(
         }),
     }
 }
+// 部分更新车库，只修改提供的字段
+// 版本号与当前不一致时拒绝写入，并在data中返回当前数据以便客户端合并
+#[command]
+pub fn patch_garage_overview(app: AppHandle, patch: GaragePatch) -> Result<ApiResponse<GarageOverview>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_patch_garage(&app_dir, &patch) {
+        Ok(PatchOutcome::Updated(garage)) => Ok(ApiResponse {
+            success: true,
+            data: Some(garage),
+            error: None,
+        }),
+        Ok(PatchOutcome::Conflict(e, current)) => Ok(ApiResponse {
+            success: false,
+            data: Some(current),
+            error: Some(e),
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 按ID读取车库
+fn find_garage(conn: &Connection, id: i32) -> Result<Option<GarageOverview>> {
+    Ok(load_garages_from(conn, "main")?.into_iter().find(|garage| garage.id == Some(id)))
+}
+
+// 执行车库部分更新
+fn run_patch_garage(app_dir: &Path, patch: &GaragePatch) -> Result<PatchOutcome<GarageOverview>, String> {
+    let mut conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let current = find_garage(&tx, patch.id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("车库不存在: {}", patch.id))?;
+    if let Err(e) = check_version(&patch.id.to_string(), current.version, patch.version) {
+        return Ok(PatchOutcome::Conflict(e, current));
+    }
+    if let Some(vehicle_list) = &patch.vehicle_list {
+        parse_vehicle_list(Some(vehicle_list))?;
+    }
+
+    let mut columns: Vec<&str> = Vec::new();
+    let mut values: Vec<SqlValue> = Vec::new();
+    for (column, value) in [
+        ("garage_name", &patch.garage_name),
+        ("garage_name_en", &patch.garage_name_en),
+        ("vehicle_list", &patch.vehicle_list),
+        ("remarks", &patch.remarks),
+        ("garage_type", &patch.garage_type),
+    ] {
+        if let Some(value) = value {
+            columns.push(column);
+            values.push(SqlValue::Text(value.clone()));
+        }
+    }
+    for (column, value) in [("num", patch.num), ("garage_order", patch.garage_order)] {
+        if let Some(value) = value {
+            columns.push(column);
+            values.push(SqlValue::Integer(value.into()));
+        }
+    }
+    if columns.is_empty() {
+        return Ok(PatchOutcome::Updated(current));
+    }
+
+    let set_clause: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| format!("{} = ?{}", column, index + 1))
+        .collect();
+    values.push(SqlValue::Integer(patch.id.into()));
+    values.push(SqlValue::Integer(patch.version));
+    // WHERE中再次比较版本号，防止检查与写入之间被其他连接修改
+    let changed = tx
+        .execute(
+            &format!(
+                "UPDATE garage_overview SET {}, version = version + 1 WHERE id = ?{} AND version = ?{}",
+                set_clause.join(", "),
+                values.len() - 1,
+                values.len()
+            ),
+            params_from_iter(values),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let updated = find_garage(&tx, patch.id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("车库不存在: {}", patch.id))?;
+    if changed == 0 {
+        let e = check_version(&patch.id.to_string(), updated.version, patch.version).err().unwrap_or_default();
+        return Ok(PatchOutcome::Conflict(e, updated));
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(PatchOutcome::Updated(updated))
+}
+
 // API接口函数 - 车库管理
 
 // 删除车库概览
@@ -242,7 +366,10 @@ pub fn delete_garage_overview(app: AppHandle, id: i32) -> Result<ApiResponse<()>
         Ok(mut conn) => {
             match conn.transaction() {
                 Ok(tx) => {
-                    match tx.execute("DELETE FROM garage_overview WHERE id = ?1", params![id]) {
+                    // 以该车库为目标的愿望清单条目改为不指定车库
+                    match tx.execute("DELETE FROM garage_overview WHERE id = ?1", params![id]).and_then(|_| {
+                        tx.execute("UPDATE wishlist SET target_garage_id = NULL WHERE target_garage_id = ?1", params![id])
+                    }) {
                         Ok(_) => match tx.commit() {
                             Ok(_) => Ok(ApiResponse {
                                 success: true,
@@ -280,3 +407,431 @@ pub fn delete_garage_overview(app: AppHandle, id: i32) -> Result<ApiResponse<()>
         }),
     }
 }
+
+// 解析车库的vehicle_list JSON字符串，空值视为空列表
+pub(crate) fn parse_vehicle_list(vehicle_list: Option<&str>) -> Result<Vec<Value>, String> {
+    match vehicle_list {
+        Some(raw) if !raw.trim().is_empty() => match serde_json::from_str::<Vec<Value>>(raw) {
+            Ok(list) => Ok(list),
+            Err(e) => Err(format!("车库载具列表解析失败: {}", e)),
+        },
+        _ => Ok(Vec::new()),
+    }
+}
+
+// 判断车位是否为空（前端使用空对象表示空车位）
+pub(crate) fn is_empty_slot(slot: &Value) -> bool {
+    match slot {
+        Value::Null => true,
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+// 获取车位中载具的目录ID
+pub(crate) fn slot_vehicle_id(slot: &Value) -> Option<String> {
+    slot.get("id").and_then(|id| id.as_str()).map(|id| id.to_string())
+}
+
+// 汇总所有车库中已拥有的载具ID
+pub(crate) fn owned_vehicle_ids(garages: &[GarageOverview]) -> HashSet<String> {
+    garages
+        .iter()
+        .filter_map(|garage| parse_vehicle_list(garage.vehicle_list.as_deref()).ok())
+        .flatten()
+        .filter_map(|slot| slot_vehicle_id(&slot))
+        .collect()
+}
+
+// 逐个车位修改指定库中的车库，f返回true表示该车位被修改，返回所有被修改的车位
+pub(crate) fn rewrite_garage_slots<F>(conn: &Connection, schema: &str, change_kind: &str, mut f: F) -> Result<Vec<AffectedSlot>>
+where
+    F: FnMut(&mut Map<String, Value>) -> bool,
+{
+    let mut affected = Vec::new();
+    for garage in load_garages_from(conn, schema)? {
+        let garage_id = match garage.id {
+            Some(id) => id,
+            None => continue,
+        };
+        // 无法解析的车库留给数据检查处理
+        let mut slots = match parse_vehicle_list(garage.vehicle_list.as_deref()) {
+            Ok(slots) => slots,
+            Err(_) => continue,
+        };
+        let mut changed = false;
+        for (slot_index, slot) in slots.iter_mut().enumerate() {
+            let vehicle_id = slot_vehicle_id(slot);
+            if let (Some(vehicle_id), Value::Object(map)) = (vehicle_id, slot) {
+                if f(map) {
+                    changed = true;
+                    affected.push(AffectedSlot {
+                        garage_id,
+                        garage_name: garage.garage_name.clone(),
+                        slot: slot_index,
+                        vehicle_id,
+                        change_kind: change_kind.to_string(),
+                    });
+                }
+            }
+        }
+        if changed {
+            let vehicle_list = serde_json::to_string(&slots).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            conn.execute(
+                &format!("UPDATE {}.garage_overview SET vehicle_list = ?1, version = version + 1 WHERE id = ?2", schema),
+                params![vehicle_list, garage_id],
+            )?;
+        }
+    }
+    Ok(affected)
+}
+
+// 按前端车位格式生成载具快照
+pub(crate) fn vehicle_slot(vehicle: &VehicleOverview, brand: Option<&VehicleBrand>) -> Value {
+    json!({
+        "id": vehicle.id,
+        "vehicleName": vehicle.vehicle_name,
+        "vehicleNameEn": vehicle.vehicle_name_en,
+        "feature": vehicle.feature.clone().unwrap_or_default(),
+        "brandName": brand.and_then(|b| b.brand_name.clone()),
+        "brandNameEn": brand.and_then(|b| b.brand_name_en.clone()),
+        "price": vehicle.price,
+        "vehicle_type": vehicle.vehicle_type,
+        "remarks": "",
+        "vehicle_name": vehicle.vehicle_name,
+        "vehicle_name_en": vehicle.vehicle_name_en,
+    })
+}
+
+// 读取当前角色的车库（按garage_order排序）
+pub(crate) fn load_garages(conn: &Connection) -> Result<Vec<GarageOverview>> {
+    query_garages(conn, "main", &format!("WHERE profile_id = {}", ACTIVE_PROFILE))
+}
+
+// 附加用户数据库，使目录与车库的修改能在同一事务中完成
+pub(crate) fn attach_user_db(conn: &Connection, app_dir: &Path) -> Result<()> {
+    let user_db_path = app_dir.join("gtavm_user.db");
+    conn.execute(
+        "ATTACH DATABASE ?1 AS user_db",
+        params![user_db_path.to_string_lossy().to_string()],
+    )?;
+    Ok(())
+}
+
+// 从指定库（main或附加的user_db）读取所有角色的车库
+pub(crate) fn load_garages_from(conn: &Connection, schema: &str) -> Result<Vec<GarageOverview>> {
+    query_garages(conn, schema, "")
+}
+
+fn query_garages(conn: &Connection, schema: &str, profile_filter: &str) -> Result<Vec<GarageOverview>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, garage_name, garage_name_en, num, vehicle_list, remarks, garage_order, garage_type, version FROM {}.garage_overview {} ORDER BY garage_order ASC",
+        schema, profile_filter
+    ))?;
+    let garage_iter = stmt.query_map([], |row| {
+        Ok(GarageOverview {
+            id: Some(row.get(0)?),
+            garage_name: row.get(1)?,
+            garage_name_en: row.get(2)?,
+            num: row.get(3)?,
+            vehicle_list: row.get(4)?,
+            remarks: row.get(5)?,
+            garage_order: row.get(6)?,
+            garage_type: row.get(7)?,
+            version: row.get(8)?,
+        })
+    })?;
+    garage_iter.collect()
+}
+
+// 计算调整容量后的车库状态，返回被修改的车库下标与载具移动记录
+fn apply_resize(
+    garages: &mut [GarageOverview],
+    id: i32,
+    new_num: usize,
+    policy: &OverflowPolicy,
+) -> Result<(Vec<usize>, Vec<SlotMove>), String> {
+    let source_index = match garages.iter().position(|g| g.id == Some(id)) {
+        Some(index) => index,
+        None => return Err("车库不存在".to_string()),
+    };
+    let mut source_list = parse_vehicle_list(garages[source_index].vehicle_list.as_deref())?;
+
+    // 取出超出新容量的载具，超出部分的空车位直接丢弃
+    let overflow: Vec<(usize, Value)> = if source_list.len() > new_num {
+        source_list
+            .split_off(new_num)
+            .into_iter()
+            .enumerate()
+            .map(|(offset, slot)| (new_num + offset, slot))
+            .filter(|(_, slot)| !is_empty_slot(slot))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    // 扩容时用空车位补齐
+    source_list.resize(new_num, Value::Object(Map::new()));
+
+    let mut changed = vec![source_index];
+    let mut moves = Vec::new();
+
+    if !overflow.is_empty() {
+        let candidates: Vec<usize> = match policy {
+            OverflowPolicy::Refuse => {
+                return Err(format!("缩小容量将溢出{}辆载具", overflow.len()));
+            }
+            OverflowPolicy::MoveToGarage { garage_id } => {
+                if *garage_id == id {
+                    return Err("目标车库不能是当前车库".to_string());
+                }
+                match garages.iter().position(|g| g.id == Some(*garage_id)) {
+                    Some(index) => vec![index],
+                    None => return Err("目标车库不存在".to_string()),
+                }
+            }
+            OverflowPolicy::FirstFree => (0..garages.len()).filter(|&i| i != source_index).collect(),
+        };
+
+        let mut pending = overflow.into_iter();
+        let mut next = pending.next();
+        for index in candidates {
+            if next.is_none() {
+                break;
+            }
+            let mut list = match parse_vehicle_list(garages[index].vehicle_list.as_deref()) {
+                Ok(list) => list,
+                // 指定车库数据损坏时直接报错，自动分配时跳过该车库
+                Err(e) => match policy {
+                    OverflowPolicy::MoveToGarage { .. } => return Err(e),
+                    _ => continue,
+                },
+            };
+            let capacity = garages[index].num.unwrap_or(0).max(0) as usize;
+            if list.len() < capacity {
+                list.resize(capacity, Value::Object(Map::new()));
+            }
+
+            let mut touched = false;
+            for (slot_index, slot) in list.iter_mut().enumerate().take(capacity) {
+                if !is_empty_slot(slot) {
+                    continue;
+                }
+                match next.take() {
+                    Some((from_slot, vehicle)) => {
+                        moves.push(SlotMove {
+                            vehicle_id: slot_vehicle_id(&vehicle),
+                            from_garage_id: id,
+                            from_slot,
+                            to_garage_id: garages[index].id.unwrap_or_default(),
+                            to_slot: slot_index,
+                        });
+                        *slot = vehicle;
+                        touched = true;
+                        next = pending.next();
+                    }
+                    None => break,
+                }
+            }
+
+            if touched {
+                garages[index].vehicle_list = Some(serde_json::to_string(&list).map_err(|e| e.to_string())?);
+                changed.push(index);
+            }
+        }
+
+        if next.is_some() {
+            return Err("目标车库空位不足，无法容纳溢出的载具".to_string());
+        }
+    }
+
+    garages[source_index].num = Some(new_num as i32);
+    garages[source_index].vehicle_list = Some(serde_json::to_string(&source_list).map_err(|e| e.to_string())?);
+
+    Ok((changed, moves))
+}
+
+// 调整车库容量，并按策略处理溢出的载具
+#[command]
+pub fn resize_garage(
+    app: AppHandle,
+    id: i32,
+    new_num: i32,
+    overflow_policy: OverflowPolicy,
+) -> Result<ApiResponse<ResizeGarageResult>, String> {
+    if new_num < 0 {
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("车库容量不能为负数".to_string()),
+        });
+    }
+
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_resize_garage(&app_dir, id, new_num as usize, &overflow_policy) {
+        Ok(result) => Ok(ApiResponse {
+            success: true,
+            data: Some(result),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_resize_garage(
+    app_dir: &Path,
+    id: i32,
+    new_num: usize,
+    overflow_policy: &OverflowPolicy,
+) -> Result<ResizeGarageResult, String> {
+    let mut conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let result = resize_garage_in(&tx, id, new_num, overflow_policy)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+// 在当前角色的车库中调整容量并保存所有被修改的车库
+fn resize_garage_in(
+    conn: &Connection,
+    id: i32,
+    new_num: usize,
+    overflow_policy: &OverflowPolicy,
+) -> Result<ResizeGarageResult, String> {
+    let mut garages = load_garages(conn).map_err(|e| e.to_string())?;
+    let (changed, moves) = apply_resize(&mut garages, id, new_num, overflow_policy)?;
+    for index in &changed {
+        let garage = &mut garages[*index];
+        garage.version = conn
+            .query_row(
+                "UPDATE garage_overview SET num = ?1, vehicle_list = ?2, version = version + 1 WHERE id = ?3 RETURNING version",
+                params![garage.num, garage.vehicle_list, garage.id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(ResizeGarageResult {
+        garage: garages.swap_remove(changed[0]),
+        moves,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::user_db;
+
+    fn add_garage(conn: &Connection, name: &str, num: i32, vehicle_list: Value, order: i32) -> i32 {
+        conn.execute(
+            "INSERT INTO garage_overview (garage_name, num, vehicle_list, garage_order, profile_id) VALUES (?1, ?2, ?3, ?4, 1)",
+            params![name, num, vehicle_list.to_string(), order],
+        )
+        .unwrap();
+        conn.last_insert_rowid() as i32
+    }
+
+    fn slots(conn: &Connection, id: i32) -> (i32, Vec<Option<String>>, i64) {
+        conn.query_row(
+            "SELECT num, vehicle_list, version FROM garage_overview WHERE id = ?1",
+            params![id],
+            |row| {
+                let list: String = row.get(1)?;
+                let ids = parse_vehicle_list(Some(&list)).unwrap().iter().map(slot_vehicle_id).collect();
+                Ok((row.get(0)?, ids, row.get(2)?))
+            },
+        )
+        .unwrap()
+    }
+
+    fn ids(list: &[Option<&str>]) -> Vec<Option<String>> {
+        list.iter().map(|id| id.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn growing_pads_with_empty_slots() {
+        let conn = user_db();
+        let id = add_garage(&conn, "A", 2, json!([{"id": "1-1"}, {}]), 1);
+        let result = resize_garage_in(&conn, id, 4, &OverflowPolicy::Refuse).unwrap();
+        assert!(result.moves.is_empty());
+        assert_eq!(result.garage.version, Some(1));
+        assert_eq!(slots(&conn, id), (4, ids(&[Some("1-1"), None, None, None]), 1));
+    }
+
+    #[test]
+    fn refuse_rejects_overflow_and_allows_empty_tail() {
+        let conn = user_db();
+        let id = add_garage(&conn, "A", 3, json!([{}, {"id": "1-1"}, {}]), 1);
+        assert!(resize_garage_in(&conn, id, 1, &OverflowPolicy::Refuse).is_err());
+        assert_eq!(slots(&conn, id).0, 3);
+
+        resize_garage_in(&conn, id, 2, &OverflowPolicy::Refuse).unwrap();
+        assert_eq!(slots(&conn, id), (2, ids(&[None, Some("1-1")]), 1));
+    }
+
+    #[test]
+    fn move_to_garage_fills_target_empty_slots() {
+        let conn = user_db();
+        let source = add_garage(&conn, "A", 3, json!([{"id": "1-1"}, {"id": "1-2"}, {"id": "1-3"}]), 1);
+        let target = add_garage(&conn, "B", 3, json!([{"id": "2-1"}, {}]), 2);
+        let result = resize_garage_in(&conn, source, 1, &OverflowPolicy::MoveToGarage { garage_id: target }).unwrap();
+
+        assert_eq!(slots(&conn, source), (1, ids(&[Some("1-1")]), 1));
+        assert_eq!(slots(&conn, target), (3, ids(&[Some("2-1"), Some("1-2"), Some("1-3")]), 1));
+        let moves: Vec<(usize, usize)> = result.moves.iter().map(|m| (m.from_slot, m.to_slot)).collect();
+        assert_eq!(moves, vec![(1, 1), (2, 2)]);
+        assert!(result.moves.iter().all(|m| m.from_garage_id == source && m.to_garage_id == target));
+    }
+
+    #[test]
+    fn move_to_garage_rejects_invalid_targets() {
+        let conn = user_db();
+        let source = add_garage(&conn, "A", 2, json!([{"id": "1-1"}, {"id": "1-2"}]), 1);
+        let full = add_garage(&conn, "B", 1, json!([{"id": "2-1"}]), 2);
+        for garage_id in [source, full, 999] {
+            assert!(resize_garage_in(&conn, source, 1, &OverflowPolicy::MoveToGarage { garage_id }).is_err());
+        }
+        assert_eq!(slots(&conn, source), (2, ids(&[Some("1-1"), Some("1-2")]), 0));
+        assert_eq!(slots(&conn, full).2, 0);
+    }
+
+    #[test]
+    fn first_free_spreads_across_garages_in_order() {
+        let conn = user_db();
+        let source = add_garage(&conn, "A", 3, json!([{"id": "1-1"}, {"id": "1-2"}, {"id": "1-3"}]), 1);
+        // 无法解析的车库会被跳过
+        add_garage(&conn, "B", 5, json!("not a list"), 2);
+        let first = add_garage(&conn, "C", 1, json!([]), 3);
+        let second = add_garage(&conn, "D", 2, json!([{"id": "2-1"}]), 4);
+
+        let result = resize_garage_in(&conn, source, 1, &OverflowPolicy::FirstFree).unwrap();
+        assert_eq!(result.moves.len(), 2);
+        assert_eq!(slots(&conn, first), (1, ids(&[Some("1-2")]), 1));
+        assert_eq!(slots(&conn, second), (2, ids(&[Some("2-1"), Some("1-3")]), 1));
+    }
+
+    #[test]
+    fn first_free_rejects_when_space_runs_out() {
+        let conn = user_db();
+        let source = add_garage(&conn, "A", 3, json!([{"id": "1-1"}, {"id": "1-2"}, {"id": "1-3"}]), 1);
+        add_garage(&conn, "B", 1, json!([]), 2);
+        assert!(resize_garage_in(&conn, source, 1, &OverflowPolicy::FirstFree).is_err());
+    }
+
+    #[test]
+    fn only_active_profile_garages_are_used() {
+        let conn = user_db();
+        conn.execute("INSERT INTO profile (profile_name, is_active) VALUES ('alt', 0)", []).unwrap();
+        let source = add_garage(&conn, "A", 2, json!([{"id": "1-1"}, {"id": "1-2"}]), 1);
+        let other = add_garage(&conn, "B", 2, json!([]), 2);
+        conn.execute("UPDATE garage_overview SET profile_id = 2 WHERE id = ?1", params![other]).unwrap();
+        assert!(resize_garage_in(&conn, source, 1, &OverflowPolicy::FirstFree).is_err());
+        assert!(resize_garage_in(&conn, source, 1, &OverflowPolicy::MoveToGarage { garage_id: other }).is_err());
+    }
+}
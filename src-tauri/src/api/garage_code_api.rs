@@ -0,0 +1,534 @@
+// 导入必要的依赖
+use std::collections::{BTreeSet, HashMap};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use rusqlite::{params, Connection};
+use serde_json::{Map, Value};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::brand_api::load_vehicle_brands;
+use crate::api::garage_api::{load_garages, load_garages_from, parse_vehicle_list, slot_vehicle_id, vehicle_slot};
+use crate::api::integrity_api::parse_vehicle_id;
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::database::ACTIVE_PROFILE;
+use crate::models::{ApiResponse, GarageCodeGarage, GarageCodePreview, GarageCodeSlot, GarageOverview};
+
+// 分享码格式版本，格式变化时递增
+// 分享码 = base64url(版本号 + 原始数据的CRC32 + deflate压缩后的原始数据)
+// 原始数据 = 车库数量, 每个车库(名称, 类型, 车位数, 已占用车位数, 每个车位(车位序号, 品牌ID, 品牌内序号))，均为变长整数
+const GARAGE_CODE_VERSION: u8 = 1;
+
+// 解码时的上限，防止异常分享码占用过多内存
+const MAX_DECODED_SIZE: u64 = 1024 * 1024;
+const MAX_GARAGE_SLOTS: u64 = 1000;
+
+// 分享码中的车库
+struct CodeGarage {
+    garage_name: Option<String>,
+    garage_type: Option<String>,
+    num: i32,
+    slots: Vec<(usize, String)>,
+}
+
+// 写入LEB128变长整数
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buffer.push(byte);
+            return;
+        }
+        buffer.push(byte | 0x80);
+    }
+}
+
+// 写入字符串，空字符串表示无值
+fn write_text(buffer: &mut Vec<u8>, text: Option<&str>) {
+    let text = text.unwrap_or("");
+    write_varint(buffer, text.len() as u64);
+    buffer.extend_from_slice(text.as_bytes());
+}
+
+// 按顺序读取原始数据
+struct CodeReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl CodeReader<'_> {
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = *self.data.get(self.position).ok_or("分享码数据不完整")?;
+            self.position += 1;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("分享码数据无效".to_string())
+    }
+
+    fn text(&mut self) -> Result<Option<String>, String> {
+        let len = self.varint()? as usize;
+        let end = self.position.checked_add(len).filter(|end| *end <= self.data.len()).ok_or("分享码数据不完整")?;
+        let text = std::str::from_utf8(&self.data[self.position..end]).map_err(|_| "分享码中的文字无效")?;
+        self.position = end;
+        Ok(Some(text.to_string()).filter(|text| !text.is_empty()))
+    }
+}
+
+// 将车库编码为分享码
+fn encode_garages(garages: &[GarageOverview]) -> Result<String, String> {
+    let mut raw = Vec::new();
+    write_varint(&mut raw, garages.len() as u64);
+    for garage in garages {
+        let slots = parse_vehicle_list(garage.vehicle_list.as_deref())?;
+        let occupied: Vec<(usize, String)> = slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot_vehicle_id(slot).map(|id| (index, id)))
+            .collect();
+
+        write_text(&mut raw, garage.garage_name.as_deref());
+        write_text(&mut raw, garage.garage_type.as_deref());
+        write_varint(&mut raw, garage.num.unwrap_or(slots.len() as i32).max(slots.len() as i32) as u64);
+        write_varint(&mut raw, occupied.len() as u64);
+        for (index, vehicle_id) in occupied {
+            let (brand_id, vehicle_index) =
+                parse_vehicle_id(&vehicle_id).ok_or_else(|| format!("载具ID无法编码: {}", vehicle_id))?;
+            write_varint(&mut raw, index as u64);
+            write_varint(&mut raw, brand_id as u64);
+            write_varint(&mut raw, vehicle_index as u64);
+        }
+    }
+
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&raw).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+
+    let mut envelope = vec![GARAGE_CODE_VERSION];
+    envelope.extend_from_slice(&crc32fast::hash(&raw).to_be_bytes());
+    envelope.extend_from_slice(&compressed);
+    Ok(URL_SAFE_NO_PAD.encode(envelope))
+}
+
+// 解码分享码，校验版本和CRC32
+fn decode_garages(code: &str) -> Result<(u8, Vec<CodeGarage>), String> {
+    let compact: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+    let envelope = URL_SAFE_NO_PAD.decode(compact).map_err(|_| "分享码格式无效".to_string())?;
+    if envelope.len() < 5 {
+        return Err("分享码格式无效".to_string());
+    }
+    let version = envelope[0];
+    if version != GARAGE_CODE_VERSION {
+        return Err(format!("不支持的分享码版本: {}", version));
+    }
+    let checksum = u32::from_be_bytes([envelope[1], envelope[2], envelope[3], envelope[4]]);
+
+    let mut raw = Vec::new();
+    DeflateDecoder::new(&envelope[5..])
+        .take(MAX_DECODED_SIZE + 1)
+        .read_to_end(&mut raw)
+        .map_err(|_| "分享码数据已损坏".to_string())?;
+    if raw.len() as u64 > MAX_DECODED_SIZE {
+        return Err("分享码数据过大".to_string());
+    }
+    if crc32fast::hash(&raw) != checksum {
+        return Err("分享码校验失败，可能复制不完整".to_string());
+    }
+
+    let mut reader = CodeReader { data: &raw, position: 0 };
+    let garage_count = reader.varint()?;
+    let mut garages = Vec::new();
+    for _ in 0..garage_count {
+        let garage_name = reader.text()?;
+        let garage_type = reader.text()?;
+        let num = reader.varint()?;
+        let occupied = reader.varint()?;
+        if num > MAX_GARAGE_SLOTS || occupied > num {
+            return Err("分享码中的车位数量无效".to_string());
+        }
+        let mut slots = Vec::new();
+        for _ in 0..occupied {
+            let slot = reader.varint()?;
+            let brand_id = reader.varint()?;
+            let vehicle_index = reader.varint()?;
+            if slot >= num {
+                return Err("分享码中的车位序号无效".to_string());
+            }
+            slots.push((slot as usize, format!("{}-{}", brand_id, vehicle_index)));
+        }
+        garages.push(CodeGarage {
+            garage_name,
+            garage_type,
+            num: num as i32,
+            slots,
+        });
+    }
+    if reader.position != raw.len() {
+        return Err("分享码数据无效".to_string());
+    }
+    Ok((version, garages))
+}
+
+// 解码并对照当前目录生成预览
+fn build_preview(app_dir: &Path, code: &str) -> Result<(GarageCodePreview, Vec<CodeGarage>), String> {
+    let (version, garages) = decode_garages(code)?;
+    let conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    let vehicle_names: HashMap<String, Option<String>> = load_vehicle_overviews(&conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|v| (v.id, v.vehicle_name))
+        .collect();
+
+    let mut unknown_vehicle_ids = BTreeSet::new();
+    let preview_garages = garages
+        .iter()
+        .map(|garage| GarageCodeGarage {
+            garage_name: garage.garage_name.clone(),
+            garage_type: garage.garage_type.clone(),
+            num: garage.num,
+            slots: garage
+                .slots
+                .iter()
+                .map(|(slot, vehicle_id)| {
+                    let known = vehicle_names.contains_key(vehicle_id);
+                    if !known {
+                        unknown_vehicle_ids.insert(vehicle_id.clone());
+                    }
+                    GarageCodeSlot {
+                        slot: *slot,
+                        vehicle_id: vehicle_id.clone(),
+                        vehicle_name: vehicle_names.get(vehicle_id).cloned().flatten(),
+                        known,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok((
+        GarageCodePreview {
+            version,
+            garages: preview_garages,
+            unknown_vehicle_ids: unknown_vehicle_ids.into_iter().collect(),
+        },
+        garages,
+    ))
+}
+
+// 将一个或多个车库编码为分享码
+#[command]
+pub fn encode_garage_code(app: AppHandle, garage_ids: Vec<i32>) -> Result<ApiResponse<String>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_user.db"))
+        .and_then(|conn| load_garages_from(&conn, "main"))
+        .map_err(|e| e.to_string())
+        .and_then(|garages| {
+            let mut by_id: HashMap<i32, GarageOverview> = garages
+                .into_iter()
+                .filter_map(|garage| garage.id.map(|id| (id, garage)))
+                .collect();
+            // 按传入顺序编码
+            let selected = garage_ids
+                .iter()
+                .map(|id| by_id.remove(id).ok_or_else(|| format!("车库不存在或重复选择: {}", id)))
+                .collect::<Result<Vec<_>, String>>()?;
+            if selected.is_empty() {
+                return Err("请选择要分享的车库".to_string());
+            }
+            encode_garages(&selected)
+        });
+
+    match result {
+        Ok(code) => Ok(ApiResponse {
+            success: true,
+            data: Some(code),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 预览分享码，列出目录中不存在的载具
+#[command]
+pub fn preview_garage_code(app: AppHandle, code: String) -> Result<ApiResponse<GarageCodePreview>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match build_preview(&app_dir, &code) {
+        Ok((preview, _)) => Ok(ApiResponse {
+            success: true,
+            data: Some(preview),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 将分享码导入为当前角色的新车库
+// 存在目录中没有的载具时默认拒绝导入，skip_unknown为true时将这些车位留空
+#[command]
+pub fn import_garage_code(
+    app: AppHandle,
+    code: String,
+    skip_unknown: Option<bool>,
+) -> Result<ApiResponse<Vec<GarageOverview>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_import_garage_code(&app_dir, &code, skip_unknown.unwrap_or(false)) {
+        Ok(garages) => Ok(ApiResponse {
+            success: true,
+            data: Some(garages),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_import_garage_code(app_dir: &Path, code: &str, skip_unknown: bool) -> Result<Vec<GarageOverview>, String> {
+    let (preview, garages) = build_preview(app_dir, code)?;
+    if !preview.unknown_vehicle_ids.is_empty() && !skip_unknown {
+        return Err(format!("目录中不存在以下载具: {}", preview.unknown_vehicle_ids.join(", ")));
+    }
+
+    let common_conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    let vehicles: HashMap<String, _> = load_vehicle_overviews(&common_conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|v| (v.id.clone(), v))
+        .collect();
+    let brands: HashMap<i32, _> = load_vehicle_brands(&common_conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|b| (b.id, b))
+        .collect();
+
+    let mut conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let max_order: Option<i32> = tx
+        .query_row(
+            &format!("SELECT MAX(garage_order) FROM garage_overview WHERE profile_id = {}", ACTIVE_PROFILE),
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut new_ids = Vec::new();
+    for (offset, garage) in garages.iter().enumerate() {
+        let mut slots = vec![Value::Object(Map::new()); garage.num as usize];
+        for (slot, vehicle_id) in &garage.slots {
+            if let Some(vehicle) = vehicles.get(vehicle_id) {
+                let brand = vehicle.brand_id.and_then(|brand_id| brands.get(&brand_id));
+                slots[*slot] = vehicle_slot(vehicle, brand);
+            }
+        }
+        let vehicle_list = serde_json::to_string(&slots).map_err(|e| e.to_string())?;
+        tx.execute(
+            &format!(
+                "INSERT INTO garage_overview (garage_name, num, vehicle_list, garage_order, garage_type, profile_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, {})",
+                ACTIVE_PROFILE
+            ),
+            params![
+                garage.garage_name,
+                garage.num,
+                vehicle_list,
+                max_order.unwrap_or(0) + 1 + offset as i32,
+                garage.garage_type
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        new_ids.push(tx.last_insert_rowid() as i32);
+    }
+
+    let created = load_garages(&tx)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|garage| garage.id.map(|id| new_ids.contains(&id)).unwrap_or(false))
+        .collect();
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按分享码格式封装原始数据，用于构造异常的分享码
+    fn envelope(raw: &[u8]) -> String {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(raw).unwrap();
+        let mut envelope = vec![GARAGE_CODE_VERSION];
+        envelope.extend_from_slice(&crc32fast::hash(raw).to_be_bytes());
+        envelope.extend_from_slice(&encoder.finish().unwrap());
+        URL_SAFE_NO_PAD.encode(envelope)
+    }
+
+    fn garage(name: &str, num: i32, vehicle_list: &str) -> GarageOverview {
+        GarageOverview {
+            id: Some(1),
+            garage_name: Some(name.to_string()),
+            garage_name_en: None,
+            num: Some(num),
+            vehicle_list: Some(vehicle_list.to_string()),
+            remarks: None,
+            garage_order: None,
+            garage_type: Some("办公室".to_string()),
+            version: Some(0),
+        }
+    }
+
+    #[test]
+    fn varint_round_trip() {
+        for value in [0, 1, 127, 128, 300, 16_383, 16_384, u32::MAX as u64, u64::MAX] {
+            let mut buffer = Vec::new();
+            write_varint(&mut buffer, value);
+            let mut reader = CodeReader { data: &buffer, position: 0 };
+            assert_eq!(reader.varint().unwrap(), value);
+            assert_eq!(reader.position, buffer.len());
+        }
+    }
+
+    #[test]
+    fn varint_encoded_length() {
+        let encoded_len = |value| {
+            let mut buffer = Vec::new();
+            write_varint(&mut buffer, value);
+            buffer.len()
+        };
+        assert_eq!(encoded_len(0), 1);
+        assert_eq!(encoded_len(127), 1);
+        assert_eq!(encoded_len(128), 2);
+        assert_eq!(encoded_len(u64::MAX), 10);
+    }
+
+    #[test]
+    fn varint_rejects_truncated_and_overlong_input() {
+        let mut reader = CodeReader { data: &[0x80, 0x80], position: 0 };
+        assert!(reader.varint().is_err());
+        let overlong = [0xff; 11];
+        let mut reader = CodeReader { data: &overlong, position: 0 };
+        assert!(reader.varint().is_err());
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let mut buffer = Vec::new();
+        write_text(&mut buffer, Some("车库 A"));
+        write_text(&mut buffer, None);
+        write_text(&mut buffer, Some(""));
+        let mut reader = CodeReader { data: &buffer, position: 0 };
+        assert_eq!(reader.text().unwrap().as_deref(), Some("车库 A"));
+        assert_eq!(reader.text().unwrap(), None);
+        assert_eq!(reader.text().unwrap(), None);
+        assert_eq!(reader.position, buffer.len());
+    }
+
+    #[test]
+    fn text_rejects_length_past_end() {
+        let mut reader = CodeReader { data: &[5, b'a', b'b'], position: 0 };
+        assert!(reader.text().is_err());
+    }
+
+    #[test]
+    fn garages_round_trip() {
+        let garages = vec![
+            garage("事务所", 4, r#"[{"id":"3-1"},{},{"id":"12-200"},null]"#),
+            garage("空车库", 10, "[]"),
+        ];
+        let code = encode_garages(&garages).unwrap();
+        let (version, decoded) = decode_garages(&code).unwrap();
+        assert_eq!(version, GARAGE_CODE_VERSION);
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].garage_name.as_deref(), Some("事务所"));
+        assert_eq!(decoded[0].garage_type.as_deref(), Some("办公室"));
+        assert_eq!(decoded[0].num, 4);
+        assert_eq!(decoded[0].slots, vec![(0, "3-1".to_string()), (2, "12-200".to_string())]);
+        assert_eq!(decoded[1].num, 10);
+        assert!(decoded[1].slots.is_empty());
+    }
+
+    #[test]
+    fn decode_ignores_whitespace() {
+        let code = encode_garages(&[garage("车库", 2, r#"[{"id":"1-1"}]"#)]).unwrap();
+        let (middle, rest) = code.split_at(code.len() / 2);
+        assert!(decode_garages(&format!(" {}\n{} ", middle, rest)).is_ok());
+    }
+
+    #[test]
+    fn encode_rejects_unparseable_vehicle_id() {
+        assert!(encode_garages(&[garage("车库", 1, r#"[{"id":"legacy"}]"#)]).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_bad_envelope() {
+        assert!(decode_garages("").is_err());
+        assert!(decode_garages("not base64!").is_err());
+        assert!(decode_garages(&URL_SAFE_NO_PAD.encode([GARAGE_CODE_VERSION, 0, 0])).is_err());
+
+        let code = encode_garages(&[garage("车库", 2, r#"[{"id":"1-1"}]"#)]).unwrap();
+        let mut bytes = URL_SAFE_NO_PAD.decode(&code).unwrap();
+        bytes[0] = GARAGE_CODE_VERSION + 1;
+        assert!(decode_garages(&URL_SAFE_NO_PAD.encode(&bytes)).is_err());
+
+        let mut bytes = URL_SAFE_NO_PAD.decode(&code).unwrap();
+        bytes[1] ^= 0xff;
+        assert!(decode_garages(&URL_SAFE_NO_PAD.encode(&bytes)).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_invalid_slot_counts() {
+        // 已占用车位数大于车位数
+        assert!(decode_garages(&envelope(&[1, 0, 0, 1, 2])).is_err());
+        // 车位序号超出车位数
+        assert!(decode_garages(&envelope(&[1, 0, 0, 1, 1, 1, 1, 1])).is_err());
+        // 车位数超过上限
+        let mut raw = vec![1, 0, 0];
+        write_varint(&mut raw, MAX_GARAGE_SLOTS + 1);
+        raw.push(0);
+        assert!(decode_garages(&envelope(&raw)).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_trailing_data() {
+        assert!(decode_garages(&envelope(&[1, 0, 0, 1, 0])).is_ok());
+        assert!(decode_garages(&envelope(&[1, 0, 0, 1, 0, 0])).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_oversized_data() {
+        let raw = vec![0; MAX_DECODED_SIZE as usize + 1];
+        assert!(decode_garages(&envelope(&raw)).is_err());
+    }
+}
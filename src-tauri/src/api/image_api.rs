@@ -0,0 +1,607 @@
+// 导入必要的依赖
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use image::ImageFormat;
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use tauri::http::{Request, Response};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::api::wiki_import_api::normalize_name;
+use crate::database::{create_vehicle_image_table, has_column};
+use crate::models::{ApiResponse, ImageImportReport, ImageImportSkip, MissingVehicleImage, VehicleImage, VehicleOverview};
+
+// 提供载具图片的自定义协议，地址格式为 gtavm-image://localhost/<载具ID>/<original|thumbnail>
+pub const IMAGE_SCHEME: &str = "gtavm-image";
+
+// 图片目录（位于应用数据目录下）和缩略图子目录
+const IMAGE_DIR: &str = "vehicle_images";
+const THUMBNAIL_DIR: &str = "thumbnails";
+
+// 缩略图的最大宽高，按原图比例缩放
+const THUMBNAIL_SIZE: u32 = 256;
+
+// 支持导入的图片格式
+const IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "webp"];
+
+// 单个图片文件的最大字节数，避免压缩包中的异常文件占满内存
+const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+
+// 解码图片时允许的最大宽高，文件很小但声明了超大尺寸的图片会在解码时占用大量内存
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+
+fn image_dir(app_dir: &Path) -> PathBuf {
+    app_dir.join(IMAGE_DIR)
+}
+
+// 缩略图统一保存为PNG，文件名与原图相同
+fn thumbnail_path(app_dir: &Path, file_name: &str) -> PathBuf {
+    let stem = Path::new(file_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    image_dir(app_dir).join(THUMBNAIL_DIR).join(format!("{}.png", stem))
+}
+
+// 生成前端使用的图片地址，Windows和Android上自定义协议以http://<协议>.localhost形式访问
+fn image_url(vehicle_id: &str, kind: &str) -> String {
+    if cfg!(any(windows, target_os = "android")) {
+        format!("http://{}.localhost/{}/{}", IMAGE_SCHEME, vehicle_id, kind)
+    } else {
+        format!("{}://localhost/{}/{}", IMAGE_SCHEME, vehicle_id, kind)
+    }
+}
+
+fn image_extension(name: &str) -> Option<String> {
+    Path::new(name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .filter(|ext| IMAGE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+// 最多读取MAX_IMAGE_BYTES字节，超出时返回空，不信任压缩包中记录的文件大小
+fn read_limited<R: Read>(reader: R) -> io::Result<Option<Vec<u8>>> {
+    let mut bytes = Vec::new();
+    reader.take(MAX_IMAGE_BYTES + 1).read_to_end(&mut bytes)?;
+    Ok(Some(bytes).filter(|bytes| bytes.len() as u64 <= MAX_IMAGE_BYTES))
+}
+
+// 按文件内容识别格式并在限制尺寸的情况下解码图片
+fn decode_image(bytes: &[u8]) -> Result<image::DynamicImage, String> {
+    let mut limits = image::Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    let mut reader = image::ImageReader::new(io::Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    reader.limits(limits);
+    reader.decode().map_err(|e| e.to_string())
+}
+
+// 读取所有载具图片
+fn load_vehicle_images(conn: &Connection) -> Result<Vec<VehicleImage>> {
+    create_vehicle_image_table(conn)?;
+    let mut stmt =
+        conn.prepare("SELECT vehicle_id, file_name, width, height, source FROM vehicle_image ORDER BY vehicle_id")?;
+    let image_iter = stmt.query_map([], |row| {
+        let vehicle_id: String = row.get(0)?;
+        Ok(VehicleImage {
+            url: image_url(&vehicle_id, "original"),
+            thumbnail_url: image_url(&vehicle_id, "thumbnail"),
+            vehicle_id,
+            file_name: row.get(1)?,
+            width: row.get(2)?,
+            height: row.get(3)?,
+            source: row.get(4)?,
+        })
+    })?;
+    image_iter.collect()
+}
+
+// 获取所有载具图片
+#[command]
+pub fn get_vehicle_images(app: AppHandle) -> Result<ApiResponse<Vec<VehicleImage>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| load_vehicle_images(&conn)) {
+        Ok(images) => Ok(ApiResponse {
+            success: true,
+            data: Some(images),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 导入时先写入的临时文件，事务提交后才替换正式文件
+fn staged_path(path: &Path) -> PathBuf {
+    let mut staged = path.as_os_str().to_owned();
+    staged.push(".importing");
+    PathBuf::from(staged)
+}
+
+// 已写入临时文件、等待事务提交的图片，previous为被替换的原图文件名
+struct PendingImage {
+    vehicle_id: String,
+    file_name: String,
+    previous: Option<String>,
+}
+
+// 删除图片原图和缩略图
+pub(crate) fn remove_image_files(app_dir: &Path, file_name: &str) {
+    let _ = fs::remove_file(image_dir(app_dir).join(file_name));
+    let _ = fs::remove_file(thumbnail_path(app_dir, file_name));
+}
+
+// 读取载具图片的文件名，图片表不存在时返回空
+pub(crate) fn vehicle_image_file(conn: &Connection, vehicle_id: &str) -> Result<Option<String>> {
+    if !has_column(conn, "vehicle_image", "file_name") {
+        return Ok(None);
+    }
+    conn.query_row(
+        "SELECT file_name FROM vehicle_image WHERE vehicle_id = ?1",
+        params![vehicle_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+// 导入过程中共享的目录数据
+struct ImageImport<'a> {
+    app_dir: &'a Path,
+    conn: &'a Connection,
+    ids: HashSet<String>,
+    // 归一化英文名称到载具ID，名称重复时为空
+    names: HashMap<String, Option<String>>,
+    existing: HashMap<String, String>,
+    overwrite: bool,
+    pending: Vec<PendingImage>,
+    report: ImageImportReport,
+}
+
+impl ImageImport<'_> {
+    fn skip(&mut self, file: &str, reason: String) {
+        self.report.skipped.push(ImageImportSkip {
+            file: file.to_string(),
+            reason,
+        });
+    }
+
+    // 文件名为载具ID或载具英文名称（忽略大小写、空格和符号）
+    fn match_vehicle(&self, file: &str) -> Result<String, String> {
+        let stem = Path::new(file)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if self.ids.contains(&stem) {
+            return Ok(stem);
+        }
+        match self.names.get(&normalize_name(&stem)) {
+            Some(Some(vehicle_id)) => Ok(vehicle_id.clone()),
+            Some(None) => Err(format!("有多个载具的英文名称为{}", stem)),
+            None => Err("找不到对应的载具".to_string()),
+        }
+    }
+
+    fn import(&mut self, file: &str, extension: &str, bytes: &[u8]) {
+        let vehicle_id = match self.match_vehicle(file) {
+            Ok(vehicle_id) => vehicle_id,
+            Err(reason) => return self.skip(file, reason),
+        };
+        if self.pending.iter().any(|image| image.vehicle_id == vehicle_id) {
+            return self.skip(file, format!("载具{}已从其他文件导入图片", vehicle_id));
+        }
+        if self.existing.contains_key(&vehicle_id) && !self.overwrite {
+            return self.skip(file, format!("载具{}已有图片", vehicle_id));
+        }
+        let file_name = new_image_file_name(self.app_dir, extension, &self.existing);
+        if let Err(reason) = self.save(&vehicle_id, &file_name, file, bytes) {
+            discard_staged(self.app_dir, &file_name);
+            return self.skip(file, reason);
+        }
+        self.pending.push(PendingImage {
+            previous: self.existing.remove(&vehicle_id),
+            vehicle_id,
+            file_name,
+        });
+    }
+
+    // 将原图和缩略图写入临时文件，并在事务中写入图片表
+    fn save(&self, vehicle_id: &str, file_name: &str, file: &str, bytes: &[u8]) -> Result<(), String> {
+        let image = decode_image(bytes).map_err(|e| format!("无法解析图片: {}", e))?;
+        fs::write(staged_path(&image_dir(self.app_dir).join(file_name)), bytes).map_err(|e| e.to_string())?;
+        image
+            .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+            .save_with_format(staged_path(&thumbnail_path(self.app_dir, file_name)), ImageFormat::Png)
+            .map_err(|e| format!("生成缩略图失败: {}", e))?;
+
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO vehicle_image (vehicle_id, file_name, width, height, source)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![vehicle_id, file_name, image.width(), image.height(), file],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+// 生成与载具ID无关的图片文件名（导入时间-序号），修改载具ID后原ID被新载具复用时不会覆盖其他载具的图片
+// 跳过已有文件、未提交的临时文件和图片表中已记录的文件名
+fn new_image_file_name(app_dir: &Path, extension: &str, existing: &HashMap<String, String>) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut index = 1;
+    loop {
+        // 缩略图按文件名主干命名，主干相同的不同格式图片也视为已占用
+        let stem = format!("{}-{}", timestamp, index);
+        let thumbnail = thumbnail_path(app_dir, &stem);
+        let taken = IMAGE_EXTENSIONS
+            .iter()
+            .map(|ext| image_dir(app_dir).join(format!("{}.{}", stem, ext)))
+            .chain([thumbnail.clone()])
+            .any(|path| path.exists() || staged_path(&path).exists())
+            || existing
+                .values()
+                .any(|name| Path::new(name).file_stem().is_some_and(|existing| existing.to_string_lossy() == stem));
+        if !taken {
+            return format!("{}.{}", stem, extension);
+        }
+        index += 1;
+    }
+}
+
+// 事务提交后用临时文件替换正式文件，并删除被替换的原图
+fn finish_pending(app_dir: &Path, pending: Vec<PendingImage>, report: &mut ImageImportReport) {
+    for image in pending {
+        let original = image_dir(app_dir).join(&image.file_name);
+        let thumbnail = thumbnail_path(app_dir, &image.file_name);
+        let renamed = fs::rename(staged_path(&original), &original)
+            .and_then(|_| fs::rename(staged_path(&thumbnail), &thumbnail));
+        if let Err(e) = renamed {
+            report.skipped.push(ImageImportSkip {
+                file: image.file_name,
+                reason: format!("保存图片文件失败: {}", e),
+            });
+            continue;
+        }
+        if let Some(previous) = image.previous.filter(|previous| *previous != image.file_name) {
+            remove_image_files(app_dir, &previous);
+        }
+        report.imported.push(image.vehicle_id);
+    }
+}
+
+// 删除图片的临时文件，导入失败或事务未提交时调用
+fn discard_staged(app_dir: &Path, file_name: &str) {
+    let _ = fs::remove_file(staged_path(&image_dir(app_dir).join(file_name)));
+    let _ = fs::remove_file(staged_path(&thumbnail_path(app_dir, file_name)));
+}
+
+// 递归收集目录中的所有文件
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn import_from_folder(import: &mut ImageImport, folder: &Path) -> Result<(), String> {
+    let mut files = Vec::new();
+    collect_files(folder, &mut files).map_err(|e| e.to_string())?;
+    files.sort();
+    for path in files {
+        let display = path.strip_prefix(folder).unwrap_or(&path).to_string_lossy().to_string();
+        let extension = match image_extension(&display) {
+            Some(extension) => extension,
+            None => continue,
+        };
+        match fs::File::open(&path).and_then(read_limited) {
+            Ok(Some(bytes)) => import.import(&display, &extension, &bytes),
+            Ok(None) => import.skip(&display, "文件过大".to_string()),
+            Err(e) => import.skip(&display, e.to_string()),
+        }
+    }
+    Ok(())
+}
+
+fn import_from_zip(import: &mut ImageImport, archive_path: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("无法读取压缩包: {}", e))?;
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index).map_err(|e| e.to_string())?;
+        let display = entry.name().to_string();
+        let extension = match image_extension(&display) {
+            Some(extension) if !entry.is_dir() => extension,
+            _ => continue,
+        };
+        match read_limited(entry) {
+            Ok(Some(bytes)) => import.import(&display, &extension, &bytes),
+            Ok(None) => import.skip(&display, "文件过大".to_string()),
+            Err(e) => import.skip(&display, e.to_string()),
+        }
+    }
+    Ok(())
+}
+
+fn run_import_vehicle_images(app_dir: &Path, source: &Path, overwrite: bool) -> Result<ImageImportReport, String> {
+    let is_zip = source
+        .extension()
+        .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("zip"))
+        .unwrap_or(false);
+    if !(source.is_dir() || is_zip && source.is_file()) {
+        return Err("图片来源必须是文件夹或zip压缩包".to_string());
+    }
+    fs::create_dir_all(image_dir(app_dir).join(THUMBNAIL_DIR)).map_err(|e| e.to_string())?;
+
+    let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let vehicles: Vec<VehicleOverview> = load_vehicle_overviews(&tx).map_err(|e| e.to_string())?;
+    let mut names: HashMap<String, Option<String>> = HashMap::new();
+    for vehicle in &vehicles {
+        if let Some(name) = vehicle.vehicle_name_en.as_deref().map(normalize_name).filter(|name| !name.is_empty()) {
+            names
+                .entry(name)
+                .and_modify(|id| *id = None)
+                .or_insert_with(|| Some(vehicle.id.clone()));
+        }
+    }
+    let existing = load_vehicle_images(&tx)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|image| (image.vehicle_id, image.file_name))
+        .collect();
+
+    let mut import = ImageImport {
+        app_dir,
+        conn: &tx,
+        ids: vehicles.iter().map(|v| v.id.clone()).collect(),
+        names,
+        existing,
+        overwrite,
+        pending: Vec::new(),
+        report: ImageImportReport {
+            imported: Vec::new(),
+            skipped: Vec::new(),
+        },
+    };
+    let imported = if is_zip {
+        import_from_zip(&mut import, source)
+    } else {
+        import_from_folder(&mut import, source)
+    };
+    let ImageImport { pending, mut report, .. } = import;
+    if let Err(e) = imported.and_then(|_| tx.commit().map_err(|e| e.to_string())) {
+        for image in &pending {
+            discard_staged(app_dir, &image.file_name);
+        }
+        return Err(e);
+    }
+    finish_pending(app_dir, pending, &mut report);
+    Ok(report)
+}
+
+// 从本地文件夹或zip压缩包导入载具图片，并生成缩略图
+// 文件名需为载具ID或载具英文名称，overwrite为true时替换已有图片
+// 解码和缩放图片耗时较长，在后台线程中执行
+#[command(async)]
+pub fn import_vehicle_images(
+    app: AppHandle,
+    source_path: String,
+    overwrite: Option<bool>,
+) -> Result<ApiResponse<ImageImportReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_import_vehicle_images(&app_dir, Path::new(&source_path), overwrite.unwrap_or(false)) {
+        Ok(report) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 删除载具图片及其缩略图
+#[command]
+pub fn delete_vehicle_image(app: AppHandle, vehicle_id: String) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| {
+        let file_name = vehicle_image_file(&conn, &vehicle_id)?;
+        if file_name.is_some() {
+            conn.execute("DELETE FROM vehicle_image WHERE vehicle_id = ?1", params![vehicle_id])?;
+        }
+        Ok(file_name)
+    });
+
+    match result {
+        Ok(file_name) => {
+            if let Some(file_name) = file_name {
+                remove_image_files(&app_dir, &file_name);
+            }
+            Ok(ApiResponse {
+                success: true,
+                data: None,
+                error: None,
+            })
+        }
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 获取缺少图片的载具，包括已导入但文件丢失的载具
+#[command]
+pub fn get_missing_vehicle_images(app: AppHandle) -> Result<ApiResponse<Vec<MissingVehicleImage>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| {
+        let images: HashMap<String, String> = load_vehicle_images(&conn)?
+            .into_iter()
+            .map(|image| (image.vehicle_id, image.file_name))
+            .collect();
+        let mut vehicles = load_vehicle_overviews(&conn)?;
+        vehicles.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(vehicles
+            .into_iter()
+            .filter_map(|vehicle| {
+                let reason = match images.get(&vehicle.id) {
+                    None => "no_image",
+                    Some(file_name) if !image_dir(&app_dir).join(file_name).is_file() => "file_missing",
+                    Some(_) => return None,
+                };
+                Some(MissingVehicleImage {
+                    vehicle_id: vehicle.id,
+                    vehicle_name: vehicle.vehicle_name,
+                    vehicle_name_en: vehicle.vehicle_name_en,
+                    reason: reason.to_string(),
+                })
+            })
+            .collect::<Vec<_>>())
+    });
+
+    match result {
+        Ok(missing) => Ok(ApiResponse {
+            success: true,
+            data: Some(missing),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+fn image_response(status: u16, content_type: &str, body: Vec<u8>) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", content_type)
+        .header("Cache-Control", "no-cache")
+        .body(body)
+        .unwrap_or_default()
+}
+
+fn content_type(file_name: &str) -> &'static str {
+    match image_extension(file_name).as_deref() {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+// 读取缩略图，缩略图丢失时（如载具改ID后）根据原图重新生成
+fn read_thumbnail(app_dir: &Path, file_name: &str) -> Option<Vec<u8>> {
+    let path = thumbnail_path(app_dir, file_name);
+    if !path.is_file() {
+        let bytes = fs::File::open(image_dir(app_dir).join(file_name)).and_then(read_limited).ok()??;
+        let image = decode_image(&bytes).ok()?;
+        fs::create_dir_all(path.parent()?).ok()?;
+        image
+            .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+            .save_with_format(&path, ImageFormat::Png)
+            .ok()?;
+    }
+    fs::read(path).ok()
+}
+
+// 处理自定义协议请求，路径为 /<载具ID>/<original|thumbnail>
+pub fn serve_vehicle_image(app_dir: &Path, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let mut segments = request.uri().path().trim_matches('/').split('/');
+    let (vehicle_id, kind) = match (segments.next(), segments.next().unwrap_or("original"), segments.next()) {
+        (Some(vehicle_id), kind, None) if !vehicle_id.is_empty() => (vehicle_id, kind),
+        _ => return image_response(400, "text/plain", b"invalid image path".to_vec()),
+    };
+
+    let file_name: Option<String> = Connection::open_with_flags(
+        app_dir.join("gtavm_common.db"),
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .and_then(|conn| {
+        conn.query_row(
+            "SELECT file_name FROM vehicle_image WHERE vehicle_id = ?1",
+            params![vehicle_id],
+            |row| row.get(0),
+        )
+        .optional()
+    })
+    .ok()
+    .flatten();
+    let file_name = match file_name {
+        Some(file_name) => file_name,
+        None => return image_response(404, "text/plain", b"image not found".to_vec()),
+    };
+
+    let body = match kind {
+        "original" => fs::read(image_dir(app_dir).join(&file_name)).ok().map(|body| (content_type(&file_name), body)),
+        "thumbnail" => read_thumbnail(app_dir, &file_name).map(|body| ("image/png", body)),
+        _ => return image_response(400, "text/plain", b"invalid image kind".to_vec()),
+    };
+    match body {
+        Some((content_type, body)) => image_response(200, content_type, body),
+        None => image_response(404, "text/plain", b"image file missing".to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_file_names_skip_taken_names() {
+        let app_dir = std::env::temp_dir().join(format!("gtavm_image_names_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&app_dir);
+        fs::create_dir_all(image_dir(&app_dir).join(THUMBNAIL_DIR)).unwrap();
+
+        let first = new_image_file_name(&app_dir, "png", &HashMap::new());
+        fs::write(staged_path(&image_dir(&app_dir).join(&first)), b"").unwrap();
+        let second = new_image_file_name(&app_dir, "jpg", &HashMap::new());
+        assert_ne!(Path::new(&first).file_stem(), Path::new(&second).file_stem());
+
+        // 图片表中已记录但文件缺失的文件名也不会被复用
+        let existing = HashMap::from([("1-3".to_string(), second.clone())]);
+        let third = new_image_file_name(&app_dir, "jpg", &existing);
+        assert!(third != first && third != second);
+        assert!(third.ends_with(".jpg"));
+
+        let _ = fs::remove_dir_all(app_dir);
+    }
+}
@@ -0,0 +1,538 @@
+// 导入必要的依赖
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, Result};
+use serde_json::{Map, Value};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::catalog_diff_api::load_dict;
+use crate::api::garage_api::{is_empty_slot, load_garages_from, parse_vehicle_list, slot_vehicle_id};
+use crate::api::vehicle_api::split_features;
+use crate::database::record_price_changes;
+use crate::models::{ApiResponse, GarageOverview, IntegrityIssue, IntegrityReport};
+
+// 目录中一辆载具的原始数据，价格保留存储类型以便检查
+struct RawVehicle {
+    id: String,
+    brand_id: Option<i32>,
+    vehicle_name_en: Option<String>,
+    vehicle_type: Option<String>,
+    feature: Option<String>,
+    price_type: String,
+    price_text: Option<String>,
+}
+
+// 将以文本或小数存储的价格转换为整数，例如"1,250,000"或"$1250000"
+// 价格按i32读取（见vehicle_api::read_price），负数和超出i32范围的值视为无法修复
+fn parse_price_text(text: &str) -> Option<i32> {
+    let trimmed = text.trim().trim_start_matches('$');
+    if let Ok(value) = trimmed.parse::<f64>() {
+        if value.fract() == 0.0 && (0.0..=i32::MAX as f64).contains(&value) {
+            return Some(value as i32);
+        }
+        return None;
+    }
+    let digits: String = trimmed.chars().filter(|c| *c != ',' && *c != ' ').collect();
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+// 解析"品牌ID-序号"格式的载具ID
+pub(crate) fn parse_vehicle_id(id: &str) -> Option<(i32, u32)> {
+    let (brand, index) = id.split_once('-')?;
+    Some((brand.parse().ok()?, index.parse().ok()?))
+}
+
+// 检查目录数据，auto_fix为true时修复可以安全修复的问题
+fn check_catalog(conn: &Connection, auto_fix: bool) -> Result<IntegrityReport> {
+    let brands: HashSet<i32> = {
+        let mut stmt = conn.prepare("SELECT id FROM vehicle_brand")?;
+        let brand_iter = stmt.query_map([], |row| row.get(0))?;
+        brand_iter.collect::<Result<_>>()?
+    };
+    let features = load_dict(conn, "feature_type_dict")?;
+    let vehicle_types = load_dict(conn, "vehicle_type_dict")?;
+
+    let vehicles: Vec<RawVehicle> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, brand_id, vehicle_name_en, vehicle_type, feature, typeof(price), CAST(price AS TEXT) FROM vehicle_overview ORDER BY id",
+        )?;
+        let vehicle_iter = stmt.query_map([], |row| {
+            Ok(RawVehicle {
+                id: row.get(0)?,
+                brand_id: row.get(1)?,
+                vehicle_name_en: row.get(2)?,
+                vehicle_type: row.get(3)?,
+                feature: row.get(4)?,
+                price_type: row.get(5)?,
+                price_text: row.get(6)?,
+            })
+        })?;
+        vehicle_iter.collect::<Result<_>>()?
+    };
+
+    let mut issues = Vec::new();
+    let mut names: HashMap<String, Vec<String>> = HashMap::new();
+
+    for vehicle in &vehicles {
+        // 品牌不存在
+        let brand_known = vehicle.brand_id.map(|id| brands.contains(&id)).unwrap_or(false);
+        if !brand_known {
+            // ID中的品牌存在时，使用ID中的品牌修复
+            let id_brand = parse_vehicle_id(&vehicle.id)
+                .map(|(brand, _)| brand)
+                .filter(|brand| brands.contains(brand));
+            let fixed = match (auto_fix, id_brand) {
+                (true, Some(brand)) => {
+                    conn.execute(
+                        "UPDATE vehicle_overview SET brand_id = ?1, version = version + 1 WHERE id = ?2",
+                        params![brand, vehicle.id],
+                    )?;
+                    true
+                }
+                _ => false,
+            };
+            issues.push(IntegrityIssue {
+                kind: "orphan_brand".to_string(),
+                garage_id: None,
+                vehicle_id: Some(vehicle.id.clone()),
+                detail: format!("品牌ID {:?} 不存在", vehicle.brand_id),
+                fixed,
+            });
+        }
+
+        // ID与品牌不一致，需要通过重新编号修复
+        match parse_vehicle_id(&vehicle.id) {
+            Some((brand, _)) => {
+                if brand_known && vehicle.brand_id != Some(brand) {
+                    issues.push(IntegrityIssue {
+                        kind: "id_brand_mismatch".to_string(),
+                        garage_id: None,
+                        vehicle_id: Some(vehicle.id.clone()),
+                        detail: format!("ID中的品牌为{}，但brand_id为{:?}", brand, vehicle.brand_id),
+                        fixed: false,
+                    });
+                }
+            }
+            None => issues.push(IntegrityIssue {
+                kind: "id_brand_mismatch".to_string(),
+                garage_id: None,
+                vehicle_id: Some(vehicle.id.clone()),
+                detail: "ID不符合\"品牌ID-序号\"格式".to_string(),
+                fixed: false,
+            }),
+        }
+
+        // 整数价格超出范围，无法自动修复
+        if vehicle.price_type == "integer" {
+            let price: Option<i64> = vehicle.price_text.as_deref().and_then(|text| text.parse().ok());
+            if let Some(price) = price.filter(|price| *price < 0 || *price > i32::MAX as i64) {
+                issues.push(IntegrityIssue {
+                    kind: "invalid_price".to_string(),
+                    garage_id: None,
+                    vehicle_id: Some(vehicle.id.clone()),
+                    detail: format!("价格超出有效范围: {}", price),
+                    fixed: false,
+                });
+            }
+        }
+
+        // 价格不是整数
+        if vehicle.price_type != "integer" && vehicle.price_type != "null" {
+            let parsed = vehicle.price_text.as_deref().and_then(parse_price_text);
+            let fixed = match (auto_fix, parsed) {
+                (true, Some(price)) => {
+                    conn.execute(
+                        "UPDATE vehicle_overview SET price = ?1, version = version + 1 WHERE id = ?2",
+                        params![price, vehicle.id],
+                    )?;
+                    true
+                }
+                _ => false,
+            };
+            issues.push(IntegrityIssue {
+                kind: "non_integer_price".to_string(),
+                garage_id: None,
+                vehicle_id: Some(vehicle.id.clone()),
+                detail: format!(
+                    "价格以{}存储: {}",
+                    vehicle.price_type,
+                    vehicle.price_text.clone().unwrap_or_default()
+                ),
+                fixed,
+            });
+        }
+
+        // 特性不在字典中
+        if !features.is_empty() {
+            for key in split_features(vehicle.feature.as_deref()) {
+                if !features.contains_key(&key) {
+                    issues.push(IntegrityIssue {
+                        kind: "unknown_feature".to_string(),
+                        garage_id: None,
+                        vehicle_id: Some(vehicle.id.clone()),
+                        detail: format!("特性{}不在feature_type_dict中", key),
+                        fixed: false,
+                    });
+                }
+            }
+        }
+
+        // 载具类型不在字典中
+        if let Some(vehicle_type) = vehicle.vehicle_type.as_ref().filter(|t| !t.is_empty()) {
+            if !vehicle_types.is_empty() && !vehicle_types.contains_key(vehicle_type) {
+                issues.push(IntegrityIssue {
+                    kind: "unknown_vehicle_type".to_string(),
+                    garage_id: None,
+                    vehicle_id: Some(vehicle.id.clone()),
+                    detail: format!("载具类型{}不在vehicle_type_dict中", vehicle_type),
+                    fixed: false,
+                });
+            }
+        }
+
+        if let Some(name) = vehicle.vehicle_name_en.as_ref().filter(|n| !n.trim().is_empty()) {
+            names
+                .entry(name.trim().to_lowercase())
+                .or_default()
+                .push(vehicle.id.clone());
+        }
+    }
+
+    // 英文名称重复
+    let mut duplicate_names: Vec<(String, Vec<String>)> =
+        names.into_iter().filter(|(_, ids)| ids.len() > 1).collect();
+    duplicate_names.sort();
+    for (name, ids) in duplicate_names {
+        issues.push(IntegrityIssue {
+            kind: "duplicate_name_en".to_string(),
+            garage_id: None,
+            vehicle_id: None,
+            detail: format!("英文名称{}重复: {}", name, ids.join(", ")),
+            fixed: false,
+        });
+    }
+
+    let fixed_count = issues.iter().filter(|issue| issue.fixed).count();
+    // 修复后的价格记入价格历史
+    if fixed_count > 0 {
+        record_price_changes(conn, true)?;
+    }
+    Ok(IntegrityReport {
+        issues,
+        fixed_count,
+        backup_path: None,
+    })
+}
+
+// 检查载具目录数据完整性
+#[command]
+pub fn check_catalog_integrity(
+    app: AppHandle,
+    auto_fix: Option<bool>,
+) -> Result<ApiResponse<IntegrityReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    match Connection::open(&db_path) {
+        Ok(mut conn) => match conn.transaction() {
+            Ok(tx) => match check_catalog(&tx, auto_fix.unwrap_or(false)) {
+                Ok(report) => match tx.commit() {
+                    Ok(_) => Ok(ApiResponse {
+                        success: true,
+                        data: Some(report),
+                        error: None,
+                    }),
+                    Err(e) => Ok(ApiResponse {
+                        success: false,
+                        data: None,
+                        error: Some(e.to_string()),
+                    }),
+                },
+                Err(e) => {
+                    // 回滚事务
+                    let _ = tx.rollback();
+                    Ok(ApiResponse {
+                        success: false,
+                        data: None,
+                        error: Some(e.to_string()),
+                    })
+                }
+            },
+            Err(e) => Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            }),
+        },
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 备份用户数据库，返回备份文件路径
+pub(crate) fn backup_user_db(app_dir: &Path) -> std::io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let backup_path = app_dir.join(format!("gtavm_user.db.{}.bak", timestamp));
+    std::fs::copy(app_dir.join("gtavm_user.db"), &backup_path)?;
+    Ok(backup_path)
+}
+
+fn garage_issue(kind: &str, garage_id: i32, vehicle_id: Option<String>, detail: String, fixed: bool) -> IntegrityIssue {
+    IntegrityIssue {
+        kind: kind.to_string(),
+        garage_id: Some(garage_id),
+        vehicle_id,
+        detail,
+        fixed,
+    }
+}
+
+// 检查车库数据，repair为true时修复可以修复的问题
+fn check_garages(conn: &Connection, known_ids: &HashSet<String>, repair: bool) -> Result<Vec<IntegrityIssue>> {
+    // 检查所有角色的车库
+    let garages = load_garages_from(conn, "main")?;
+    let mut issues = Vec::new();
+
+    // 车库顺序在每个角色内独立编号
+    let profile_ids: HashMap<i32, Option<i32>> = {
+        let mut stmt = conn.prepare("SELECT id, profile_id FROM garage_overview")?;
+        let profile_iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        profile_iter.collect::<Result<_>>()?
+    };
+    let profile_of = |garage: &GarageOverview| garage.id.and_then(|id| profile_ids.get(&id).copied().flatten());
+
+    // garage_order在角色内重复或缺失时，修复模式下按该角色的当前顺序重新编号
+    let mut order_counts: HashMap<(Option<i32>, Option<i32>), usize> = HashMap::new();
+    for garage in &garages {
+        *order_counts.entry((profile_of(garage), garage.garage_order)).or_insert(0) += 1;
+    }
+    let order_invalid = |garage: &GarageOverview| {
+        garage.garage_order.is_none() || order_counts[&(profile_of(garage), garage.garage_order)] > 1
+    };
+    let renumber: HashSet<Option<i32>> = garages.iter().filter(|g| order_invalid(g)).map(profile_of).collect();
+    let mut positions: HashMap<Option<i32>, i32> = HashMap::new();
+
+    for garage in &garages {
+        let garage_id = garage.id.unwrap_or_default();
+        let profile_id = profile_of(garage);
+        let position = {
+            let position = positions.entry(profile_id).or_insert(0);
+            *position += 1;
+            *position
+        };
+        let capacity = garage.num.unwrap_or(0).max(0) as usize;
+        let mut garage_name = garage.garage_name.clone();
+        let mut num = garage.num;
+        let mut garage_order = garage.garage_order;
+        let mut changed = false;
+
+        // 车库名称缺失
+        if garage_name.as_deref().map(|name| name.trim().is_empty()).unwrap_or(true) {
+            if repair {
+                garage_name = Some(format!("车库{}", garage_id));
+                changed = true;
+            }
+            issues.push(garage_issue("missing_garage_name", garage_id, None, "车库名称为空".to_string(), repair));
+        }
+
+        // 车库顺序重复或缺失
+        if order_invalid(garage) {
+            issues.push(garage_issue(
+                "duplicate_garage_order",
+                garage_id,
+                None,
+                format!("车库顺序{:?}重复或缺失", garage.garage_order),
+                repair,
+            ));
+        }
+        if repair && renumber.contains(&profile_id) && garage_order != Some(position) {
+            garage_order = Some(position);
+            changed = true;
+        }
+
+        let mut slots = match parse_vehicle_list(garage.vehicle_list.as_deref()) {
+            Ok(slots) => Some(slots),
+            Err(e) => {
+                // 无法解析的载具列表需要用户手动处理，自动重置会导致车库中的载具丢失
+                issues.push(garage_issue("malformed_vehicle_list", garage_id, None, e, false));
+                None
+            }
+        };
+
+        if let Some(slots) = slots.as_mut() {
+            // 载具列表长度与容量不一致
+            if slots.len() != capacity {
+                issues.push(garage_issue(
+                    "capacity_mismatch",
+                    garage_id,
+                    None,
+                    format!("载具列表长度{}与容量{}不一致", slots.len(), capacity),
+                    repair,
+                ));
+                if repair {
+                    // 去掉末尾多余的空车位，仍然超出时扩大容量以保留载具
+                    while slots.len() > capacity && slots.last().map(is_empty_slot).unwrap_or(false) {
+                        slots.pop();
+                    }
+                    if slots.len() > capacity {
+                        num = Some(slots.len() as i32);
+                    } else {
+                        slots.resize(capacity, Value::Object(Map::new()));
+                    }
+                    changed = true;
+                }
+            }
+
+            // 载具ID缺失或不在目录中，无法自动修复
+            for (slot_index, slot) in slots.iter().enumerate() {
+                if is_empty_slot(slot) {
+                    continue;
+                }
+                match slot_vehicle_id(slot) {
+                    Some(vehicle_id) => {
+                        if !known_ids.is_empty() && !known_ids.contains(&vehicle_id) {
+                            issues.push(garage_issue(
+                                "unknown_vehicle_id",
+                                garage_id,
+                                Some(vehicle_id),
+                                format!("车位{}中的载具不在目录中", slot_index),
+                                false,
+                            ));
+                        }
+                    }
+                    None => issues.push(garage_issue(
+                        "missing_vehicle_id",
+                        garage_id,
+                        None,
+                        format!("车位{}中的载具缺少ID", slot_index),
+                        false,
+                    )),
+                }
+            }
+        }
+
+        if changed {
+            let vehicle_list = match &slots {
+                Some(slots) => serde_json::to_string(slots).ok(),
+                None => garage.vehicle_list.clone(),
+            };
+            conn.execute(
+                "UPDATE garage_overview SET garage_name = ?1, num = ?2, vehicle_list = ?3, garage_order = ?4, version = version + 1 WHERE id = ?5",
+                params![garage_name, num, vehicle_list, garage_order, garage_id],
+            )?;
+        }
+    }
+
+    Ok(issues)
+}
+
+// 检查并在需要时修复用户车库数据
+fn run_user_data_check(app_dir: &Path, repair: bool) -> Result<IntegrityReport, String> {
+    // 目录不可用时跳过载具ID检查
+    let known_ids: HashSet<String> = Connection::open(app_dir.join("gtavm_common.db"))
+        .and_then(|conn| {
+            let mut stmt = conn.prepare("SELECT id FROM vehicle_overview")?;
+            let id_iter = stmt.query_map([], |row| row.get(0))?;
+            id_iter.collect::<Result<HashSet<String>>>()
+        })
+        .unwrap_or_default();
+
+    let backup_path = if repair {
+        Some(backup_user_db(app_dir).map_err(|e| format!("备份用户数据失败: {}", e))?)
+    } else {
+        None
+    };
+
+    let mut conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let issues = check_garages(&tx, &known_ids, repair).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    let fixed_count = issues.iter().filter(|issue| issue.fixed).count();
+    Ok(IntegrityReport {
+        issues,
+        fixed_count,
+        backup_path: backup_path.map(|path| path.to_string_lossy().to_string()),
+    })
+}
+
+// 检查用户车库数据
+#[command]
+pub fn check_user_data(app: AppHandle) -> Result<ApiResponse<IntegrityReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_user_data_check(&app_dir, false) {
+        Ok(report) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 备份后修复用户车库数据
+#[command]
+pub fn repair_user_data(app: AppHandle) -> Result<ApiResponse<IntegrityReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_user_data_check(&app_dir, true) {
+        Ok(report) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_text_accepts_formatted_integers() {
+        assert_eq!(parse_price_text("1,250,000"), Some(1_250_000));
+        assert_eq!(parse_price_text(" $1250000 "), Some(1_250_000));
+        assert_eq!(parse_price_text("725000.0"), Some(725_000));
+        assert_eq!(parse_price_text("0"), Some(0));
+    }
+
+    #[test]
+    fn price_text_rejects_invalid_and_out_of_range_values() {
+        for text in ["-100", "-1,000", "1e30", "2147483648", "3,000,000,000", "99.5", "NaN", "inf", "", "abc"] {
+            assert_eq!(parse_price_text(text), None, "{} should be rejected", text);
+        }
+        assert_eq!(parse_price_text("2147483647"), Some(i32::MAX));
+    }
+}
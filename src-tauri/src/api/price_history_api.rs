@@ -0,0 +1,349 @@
+// 导入必要的依赖
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::garage_api::{load_garages, parse_vehicle_list, slot_vehicle_id};
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::database::{create_price_history_table, create_vehicle_price_table, record_price_changes};
+use crate::models::{ApiResponse, CollectionValuation, PriceHistoryEntry, VehiclePrices};
+
+// 价格种类：标准价格、交易价格、促销价格、回收价格
+// 标准价格保存在vehicle_overview.price中，其余种类保存在vehicle_price表中
+pub const PRICE_KINDS: [&str; 4] = ["standard", "trade", "sale", "sell_back"];
+
+fn map_price_history(row: &rusqlite::Row) -> Result<PriceHistoryEntry> {
+    Ok(PriceHistoryEntry {
+        id: row.get(0)?,
+        vehicle_id: row.get(1)?,
+        vehicle_name: row.get(2)?,
+        price: row.get(3)?,
+        previous_price: row.get(4)?,
+        dlc_name: row.get(5)?,
+        effective_date: row.get(6)?,
+        price_kind: row.get(7)?,
+    })
+}
+
+// 获取载具的价格变化时间线，price_kind为空时返回所有种类
+#[command]
+pub fn get_vehicle_price_history(
+    app: AppHandle,
+    vehicle_id: String,
+    price_kind: Option<String>,
+) -> Result<ApiResponse<Vec<PriceHistoryEntry>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    let result = Connection::open(&db_path).and_then(|conn| {
+        // 确保价格历史表存在
+        create_price_history_table(&conn)?;
+        let mut stmt = conn.prepare(
+            "SELECT h.id, h.vehicle_id, v.vehicle_name, h.price, h.previous_price, h.dlc_name, h.effective_date, h.price_kind
+             FROM vehicle_price_history h
+             LEFT JOIN vehicle_overview v ON v.id = h.vehicle_id
+             WHERE h.vehicle_id = ?1 AND (?2 IS NULL OR h.price_kind = ?2)
+             ORDER BY h.effective_date ASC, h.id ASC",
+        )?;
+        let history_iter = stmt.query_map(params![vehicle_id, price_kind], map_price_history)?;
+        history_iter.collect::<Result<Vec<_>>>()
+    });
+
+    match result {
+        Ok(history) => Ok(ApiResponse {
+            success: true,
+            data: Some(history),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 获取最近一次更新中所有发生变化的价格
+#[command]
+pub fn get_latest_price_changes(app: AppHandle) -> Result<ApiResponse<Vec<PriceHistoryEntry>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    let result = Connection::open(&db_path).and_then(|conn| {
+        // 确保价格历史表存在
+        create_price_history_table(&conn)?;
+        // 最近一次更新以最新一条标准价格记录的DLC和生效日期为准，首次记录的价格不算作变化
+        let mut stmt = conn.prepare(
+            "SELECT h.id, h.vehicle_id, v.vehicle_name, h.price, h.previous_price, h.dlc_name, h.effective_date, h.price_kind
+             FROM vehicle_price_history h
+             LEFT JOIN vehicle_overview v ON v.id = h.vehicle_id
+             WHERE h.previous_price IS NOT NULL
+               AND h.price_kind = 'standard'
+               AND h.dlc_name IS (SELECT dlc_name FROM vehicle_price_history WHERE price_kind = 'standard' ORDER BY id DESC LIMIT 1)
+               AND h.effective_date IS (SELECT effective_date FROM vehicle_price_history WHERE price_kind = 'standard' ORDER BY id DESC LIMIT 1)
+             ORDER BY h.vehicle_id ASC",
+        )?;
+        let history_iter = stmt.query_map([], map_price_history)?;
+        history_iter.collect::<Result<Vec<_>>>()
+    });
+
+    match result {
+        Ok(changes) => Ok(ApiResponse {
+            success: true,
+            data: Some(changes),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 读取某一非标准种类的所有载具价格
+pub(crate) fn load_kind_prices(conn: &Connection, price_kind: &str) -> Result<HashMap<String, i64>> {
+    create_vehicle_price_table(conn)?;
+    let mut stmt = conn.prepare("SELECT vehicle_id, price FROM vehicle_price WHERE price_kind = ?1")?;
+    let price_iter = stmt.query_map(params![price_kind], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    price_iter.collect()
+}
+
+// 设置非标准种类的价格并记录价格历史，price为空时删除该价格
+pub(crate) fn set_kind_price(conn: &Connection, vehicle_id: &str, price_kind: &str, price: Option<i64>) -> Result<(), String> {
+    if price_kind == "standard" || !PRICE_KINDS.contains(&price_kind) {
+        return Err(format!("价格种类{}无效", price_kind));
+    }
+    if price.map(|price| price < 0).unwrap_or(false) {
+        return Err("价格不能为负数".to_string());
+    }
+    create_vehicle_price_table(conn)
+        .and_then(|_| create_price_history_table(conn))
+        .map_err(|e| e.to_string())?;
+
+    let previous: Option<i64> = conn
+        .query_row(
+            "SELECT price FROM vehicle_price WHERE vehicle_id = ?1 AND price_kind = ?2",
+            params![vehicle_id, price_kind],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let price = match price {
+        Some(price) => price,
+        None => {
+            conn.execute(
+                "DELETE FROM vehicle_price WHERE vehicle_id = ?1 AND price_kind = ?2",
+                params![vehicle_id, price_kind],
+            )
+            .map_err(|e| e.to_string())?;
+            return Ok(());
+        }
+    };
+    if previous == Some(price) {
+        return Ok(());
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO vehicle_price (vehicle_id, price_kind, price) VALUES (?1, ?2, ?3)",
+        params![vehicle_id, price_kind, price],
+    )
+    .and_then(|_| {
+        conn.execute(
+            "INSERT INTO vehicle_price_history (vehicle_id, price, previous_price, effective_date, price_kind)
+             VALUES (?1, ?2, ?3, date('now'), ?4)",
+            params![vehicle_id, price, previous, price_kind],
+        )
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// 获取载具各种类的当前价格
+#[command]
+pub fn get_vehicle_prices(app: AppHandle, vehicle_id: String) -> Result<ApiResponse<VehiclePrices>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| {
+        let standard = load_vehicle_overviews(&conn)?
+            .into_iter()
+            .find(|v| v.id == vehicle_id)
+            .and_then(|v| v.price.map(|price| price as i64));
+        let kind_price = |kind: &str| load_kind_prices(&conn, kind).map(|mut prices| prices.remove(&vehicle_id));
+        Ok(VehiclePrices {
+            standard,
+            trade: kind_price("trade")?,
+            sale: kind_price("sale")?,
+            sell_back: kind_price("sell_back")?,
+            vehicle_id: vehicle_id.clone(),
+        })
+    });
+
+    match result {
+        Ok(prices) => Ok(ApiResponse {
+            success: true,
+            data: Some(prices),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 设置载具某一种类的价格，price为空时删除（标准价格不能删除）
+// 标准价格写入vehicle_overview.price并记为手动修改，其余种类写入vehicle_price表
+#[command]
+pub fn set_vehicle_price(
+    app: AppHandle,
+    vehicle_id: String,
+    price_kind: String,
+    price: Option<i64>,
+) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db"))
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| {
+            let tx = conn.transaction().map_err(|e| e.to_string())?;
+            let exists = tx
+                .query_row("SELECT 1 FROM vehicle_overview WHERE id = ?1", params![vehicle_id], |_| Ok(()))
+                .optional()
+                .map_err(|e| e.to_string())?;
+            if exists.is_none() {
+                return Err(format!("载具{}不存在", vehicle_id));
+            }
+            if price_kind == "standard" {
+                let price = price.ok_or_else(|| "标准价格不能为空".to_string())?;
+                let price = i32::try_from(price).map_err(|_| "价格超出范围".to_string())?;
+                tx.execute(
+                    "UPDATE vehicle_overview SET price = ?1, version = version + 1 WHERE id = ?2 AND price IS NOT ?1",
+                    params![price, vehicle_id],
+                )
+                .and_then(|_| record_price_changes(&tx, true))
+                .map_err(|e| e.to_string())?;
+            } else {
+                set_kind_price(&tx, &vehicle_id, &price_kind, price)?;
+            }
+            tx.commit().map_err(|e| e.to_string())
+        });
+
+    match result {
+        Ok(_) => Ok(ApiResponse {
+            success: true,
+            data: None,
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 按指定价格种类计算当前角色所有车位中载具的总价值
+// fallback为true时，缺少该种类价格的载具改用标准价格
+// 回收价远低于标准价格，不使用标准价格代替，缺少回收价的载具计入missing_vehicle_ids
+#[command]
+pub fn get_collection_valuation(
+    app: AppHandle,
+    price_basis: String,
+    fallback: Option<bool>,
+) -> Result<ApiResponse<CollectionValuation>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_collection_valuation(&app_dir, &price_basis, fallback.unwrap_or(false)) {
+        Ok(valuation) => Ok(ApiResponse {
+            success: true,
+            data: Some(valuation),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_collection_valuation(app_dir: &Path, price_basis: &str, fallback: bool) -> Result<CollectionValuation, String> {
+    if !PRICE_KINDS.contains(&price_basis) {
+        return Err(format!("价格种类{}无效，可选值为{}", price_basis, PRICE_KINDS.join("、")));
+    }
+
+    let common_conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    let standard: HashMap<String, i64> = load_vehicle_overviews(&common_conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter_map(|v| v.price.map(|price| (v.id, price as i64)))
+        .collect();
+    let prices = if price_basis == "standard" {
+        HashMap::new()
+    } else {
+        load_kind_prices(&common_conn, price_basis).map_err(|e| e.to_string())?
+    };
+
+    let user_conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let garages = load_garages(&user_conn).map_err(|e| e.to_string())?;
+
+    let mut total_value = 0;
+    let mut vehicle_count = 0;
+    let mut fallback_count = 0;
+    let mut missing = BTreeSet::new();
+    for garage in &garages {
+        let slots = parse_vehicle_list(garage.vehicle_list.as_deref()).unwrap_or_default();
+        for vehicle_id in slots.iter().filter_map(slot_vehicle_id) {
+            vehicle_count += 1;
+            let price = if price_basis == "standard" {
+                standard.get(&vehicle_id).copied()
+            } else {
+                match prices.get(&vehicle_id) {
+                    Some(price) => Some(*price),
+                    None if fallback && price_basis != "sell_back" => {
+                        let price = standard.get(&vehicle_id).copied();
+                        if price.is_some() {
+                            fallback_count += 1;
+                        }
+                        price
+                    }
+                    None => None,
+                }
+            };
+            match price {
+                Some(price) => total_value += price,
+                None => {
+                    missing.insert(vehicle_id);
+                }
+            }
+        }
+    }
+
+    Ok(CollectionValuation {
+        price_basis: price_basis.to_string(),
+        total_value,
+        vehicle_count,
+        fallback_count,
+        missing_vehicle_ids: missing.into_iter().collect(),
+    })
+}
@@ -0,0 +1,483 @@
+// 导入必要的依赖
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::garage_api::{parse_vehicle_list, slot_vehicle_id};
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::database::ACTIVE_PROFILE;
+use crate::models::{ApiResponse, Profile, ProfileStats, ProfileStatsReport};
+
+// 读取所有角色（按sort_order排序）
+fn load_profiles(conn: &Connection) -> Result<Vec<Profile>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, profile_name, remarks, sort_order, is_active FROM profile ORDER BY sort_order ASC, id ASC",
+    )?;
+    let profile_iter = stmt.query_map([], |row| {
+        Ok(Profile {
+            id: Some(row.get(0)?),
+            profile_name: row.get(1)?,
+            remarks: row.get(2)?,
+            sort_order: row.get(3)?,
+            is_active: row.get::<_, i64>(4)? != 0,
+        })
+    })?;
+    profile_iter.collect()
+}
+
+// 检查角色是否存在
+fn profile_exists(conn: &Connection, id: i32) -> Result<bool> {
+    conn.query_row("SELECT COUNT(*) FROM profile WHERE id = ?1", params![id], |row| {
+        row.get::<_, i64>(0)
+    })
+    .map(|count| count > 0)
+}
+
+// 角色名称不能为空
+fn validate_profile_name(profile_name: &str) -> Result<(), String> {
+    if profile_name.trim().is_empty() {
+        return Err("角色名称不能为空".to_string());
+    }
+    Ok(())
+}
+
+// 新增角色，新角色默认排在最后，返回新角色ID
+fn insert_profile(conn: &Connection, profile_name: &str, remarks: Option<&str>) -> Result<i32> {
+    let max_order: Option<i32> = conn.query_row("SELECT MAX(sort_order) FROM profile", [], |row| row.get(0))?;
+    conn.execute(
+        "INSERT INTO profile (profile_name, remarks, sort_order, is_active) VALUES (?1, ?2, ?3, 0)",
+        params![profile_name.trim(), remarks, max_order.map(|o| o + 1).unwrap_or(1)],
+    )?;
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+// 执行用户数据库中的角色操作
+fn with_user_db<T, F>(app: &AppHandle, f: F) -> Result<ApiResponse<T>, String>
+where
+    F: FnOnce(&Connection) -> Result<T, String>,
+{
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_user.db"))
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| {
+            let tx = conn.transaction().map_err(|e| e.to_string())?;
+            let value = f(&tx)?;
+            tx.commit().map_err(|e| e.to_string())?;
+            Ok(value)
+        });
+
+    match result {
+        Ok(value) => Ok(ApiResponse {
+            success: true,
+            data: Some(value),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 获取所有角色
+#[command]
+pub fn get_profiles(app: AppHandle) -> Result<ApiResponse<Vec<Profile>>, String> {
+    with_user_db(&app, |conn| load_profiles(conn).map_err(|e| e.to_string()))
+}
+
+// 创建角色
+#[command]
+pub fn create_profile(app: AppHandle, profile: Profile) -> Result<ApiResponse<Profile>, String> {
+    with_user_db(&app, |conn| {
+        validate_profile_name(&profile.profile_name)?;
+        let id = insert_profile(conn, &profile.profile_name, profile.remarks.as_deref()).map_err(|e| e.to_string())?;
+        load_profiles(conn)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .find(|p| p.id == Some(id))
+            .ok_or_else(|| "角色创建失败".to_string())
+    })
+}
+
+// 重命名角色
+#[command]
+pub fn rename_profile(app: AppHandle, id: i32, profile_name: String) -> Result<ApiResponse<()>, String> {
+    with_user_db(&app, |conn| {
+        validate_profile_name(&profile_name)?;
+        let changed = conn
+            .execute(
+                "UPDATE profile SET profile_name = ?1 WHERE id = ?2",
+                params![profile_name.trim(), id],
+            )
+            .map_err(|e| e.to_string())?;
+        if changed == 0 {
+            return Err("角色不存在".to_string());
+        }
+        Ok(())
+    })
+}
+
+// 切换当前角色，之后的车库、愿望清单和设置操作均针对该角色
+#[command]
+pub fn switch_profile(app: AppHandle, id: i32) -> Result<ApiResponse<()>, String> {
+    with_user_db(&app, |conn| {
+        if !profile_exists(conn, id).map_err(|e| e.to_string())? {
+            return Err("角色不存在".to_string());
+        }
+        conn.execute("UPDATE profile SET is_active = (id = ?1)", params![id])
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    })
+}
+
+// 复制角色，包括其车库、愿望清单、改装记录和设置
+#[command]
+pub fn duplicate_profile(app: AppHandle, id: i32, profile_name: String) -> Result<ApiResponse<Profile>, String> {
+    with_user_db(&app, |conn| {
+        validate_profile_name(&profile_name)?;
+        let remarks: Option<String> = conn
+            .query_row("SELECT remarks FROM profile WHERE id = ?1", params![id], |row| row.get(0))
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "角色不存在".to_string())?;
+        let new_id = copy_profile(conn, id, &profile_name, remarks.as_deref()).map_err(|e| e.to_string())?;
+        load_profiles(conn)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .find(|p| p.id == Some(new_id))
+            .ok_or_else(|| "角色复制失败".to_string())
+    })
+}
+
+fn copy_profile(conn: &Connection, source_id: i32, profile_name: &str, remarks: Option<&str>) -> Result<i32> {
+    let new_id = insert_profile(conn, profile_name, remarks)?;
+
+    // 逐个复制车库，记录新旧车库ID的对应关系，用于愿望清单的目标车库和改装记录
+    let garage_ids: Vec<i32> = {
+        let mut stmt = conn.prepare("SELECT id FROM garage_overview WHERE profile_id = ?1 ORDER BY garage_order ASC")?;
+        let id_iter = stmt.query_map(params![source_id], |row| row.get(0))?;
+        id_iter.collect::<Result<_>>()?
+    };
+    let mut garage_map: HashMap<i32, i32> = HashMap::new();
+    for garage_id in garage_ids {
+        conn.execute(
+            "INSERT INTO garage_overview (garage_name, garage_name_en, num, vehicle_list, remarks, garage_order, garage_type, profile_id)
+             SELECT garage_name, garage_name_en, num, vehicle_list, remarks, garage_order, garage_type, ?1
+             FROM garage_overview WHERE id = ?2",
+            params![new_id, garage_id],
+        )?;
+        garage_map.insert(garage_id, conn.last_insert_rowid() as i32);
+    }
+
+    conn.execute(
+        "INSERT INTO wishlist (vehicle_id, priority, target_garage_id, notes, sort_order, profile_id)
+         SELECT vehicle_id, priority, target_garage_id, notes, sort_order, ?1 FROM wishlist WHERE profile_id = ?2",
+        params![new_id, source_id],
+    )?;
+    // 新车库ID均大于已有ID，逐个替换不会互相覆盖
+    for (old_garage_id, new_garage_id) in &garage_map {
+        conn.execute(
+            "UPDATE wishlist SET target_garage_id = ?1 WHERE profile_id = ?2 AND target_garage_id = ?3",
+            params![new_garage_id, new_id, old_garage_id],
+        )?;
+    }
+    conn.execute(
+        "UPDATE wishlist SET target_garage_id = NULL
+         WHERE profile_id = ?1 AND target_garage_id NOT IN (SELECT id FROM garage_overview WHERE profile_id = ?1)",
+        params![new_id],
+    )?;
+
+    // 改装记录指向复制后的车库，已删除车库的记录保留原车库ID
+    conn.execute(
+        "INSERT INTO vehicle_conversion
+            (profile_id, garage_id, slot, from_vehicle_id, to_vehicle_id, upgrade_kind, upgrade_cost, converted_at)
+         SELECT ?1, garage_id, slot, from_vehicle_id, to_vehicle_id, upgrade_kind, upgrade_cost, converted_at
+         FROM vehicle_conversion WHERE profile_id = ?2 ORDER BY id",
+        params![new_id, source_id],
+    )?;
+    for (old_garage_id, new_garage_id) in &garage_map {
+        conn.execute(
+            "UPDATE vehicle_conversion SET garage_id = ?1 WHERE profile_id = ?2 AND garage_id = ?3",
+            params![new_garage_id, new_id, old_garage_id],
+        )?;
+    }
+
+    conn.execute(
+        "INSERT INTO profile_setting (profile_id, setting_key, setting_value)
+         SELECT ?1, setting_key, setting_value FROM profile_setting WHERE profile_id = ?2",
+        params![new_id, source_id],
+    )?;
+    Ok(new_id)
+}
+
+// 删除角色及其车库、愿望清单、改装记录和设置，至少保留一个角色
+#[command]
+pub fn delete_profile(app: AppHandle, id: i32) -> Result<ApiResponse<()>, String> {
+    with_user_db(&app, |conn| {
+        if !profile_exists(conn, id).map_err(|e| e.to_string())? {
+            return Err("角色不存在".to_string());
+        }
+        let profile_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM profile", [], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        if profile_count <= 1 {
+            return Err("至少需要保留一个角色".to_string());
+        }
+
+        for sql in [
+            "DELETE FROM garage_overview WHERE profile_id = ?1",
+            "DELETE FROM wishlist WHERE profile_id = ?1",
+            "DELETE FROM vehicle_conversion WHERE profile_id = ?1",
+            "DELETE FROM profile_setting WHERE profile_id = ?1",
+            "DELETE FROM profile WHERE id = ?1",
+        ] {
+            conn.execute(sql, params![id]).map_err(|e| e.to_string())?;
+        }
+        // 删除的是当前角色时切换到第一个角色
+        conn.execute(
+            "UPDATE profile SET is_active = 1
+             WHERE id = (SELECT MIN(id) FROM profile) AND NOT EXISTS (SELECT 1 FROM profile WHERE is_active = 1)",
+            [],
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    })
+}
+
+// 获取当前角色的设置
+#[command]
+pub fn get_profile_settings(app: AppHandle) -> Result<ApiResponse<BTreeMap<String, Option<String>>>, String> {
+    with_user_db(&app, |conn| {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT setting_key, setting_value FROM profile_setting WHERE profile_id = {}",
+                ACTIVE_PROFILE
+            ))
+            .map_err(|e| e.to_string())?;
+        let setting_iter = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        setting_iter.collect::<Result<_>>().map_err(|e| e.to_string())
+    })
+}
+
+// 保存当前角色的设置，value为空时删除该设置
+#[command]
+pub fn set_profile_setting(app: AppHandle, key: String, value: Option<String>) -> Result<ApiResponse<()>, String> {
+    with_user_db(&app, |conn| {
+        let result = match value {
+            Some(value) => conn.execute(
+                &format!(
+                    "INSERT OR REPLACE INTO profile_setting (profile_id, setting_key, setting_value) VALUES ({}, ?1, ?2)",
+                    ACTIVE_PROFILE
+                ),
+                params![key, value],
+            ),
+            None => conn.execute(
+                &format!("DELETE FROM profile_setting WHERE profile_id = {} AND setting_key = ?1", ACTIVE_PROFILE),
+                params![key],
+            ),
+        };
+        result.map(|_| ()).map_err(|e| e.to_string())
+    })
+}
+
+// 统计所有角色的车库、载具、愿望清单和载具总价值
+#[command]
+pub fn get_profile_stats(app: AppHandle) -> Result<ApiResponse<ProfileStatsReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match build_profile_stats(&app_dir) {
+        Ok(report) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn build_profile_stats(app_dir: &Path) -> Result<ProfileStatsReport, String> {
+    // 目录不可用时价值按0计算
+    let prices: HashMap<String, i64> = Connection::open(app_dir.join("gtavm_common.db"))
+        .and_then(|conn| load_vehicle_overviews(&conn))
+        .map(|vehicles| {
+            vehicles
+                .into_iter()
+                .map(|v| (v.id, v.price.unwrap_or(0) as i64))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let profiles = load_profiles(&conn).map_err(|e| e.to_string())?;
+    let garages: Vec<(Option<i32>, Option<i32>, Option<String>)> = {
+        let mut stmt = conn
+            .prepare("SELECT profile_id, num, vehicle_list FROM garage_overview")
+            .map_err(|e| e.to_string())?;
+        let garage_iter = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| e.to_string())?;
+        garage_iter.collect::<Result<_>>().map_err(|e| e.to_string())?
+    };
+    let wishlist_counts: HashMap<i32, usize> = {
+        let mut stmt = conn
+            .prepare("SELECT profile_id, COUNT(*) FROM wishlist WHERE profile_id IS NOT NULL GROUP BY profile_id")
+            .map_err(|e| e.to_string())?;
+        let count_iter = stmt
+            .query_map([], |row| Ok((row.get::<_, i32>(0)?, row.get::<_, i64>(1)? as usize)))
+            .map_err(|e| e.to_string())?;
+        count_iter.collect::<Result<_>>().map_err(|e| e.to_string())?
+    };
+
+    let mut unique_vehicles: HashSet<String> = HashSet::new();
+    let mut stats: Vec<ProfileStats> = Vec::new();
+    for profile in profiles {
+        let profile_id = match profile.id {
+            Some(id) => id,
+            None => continue,
+        };
+        let mut entry = ProfileStats {
+            profile_id,
+            profile_name: profile.profile_name,
+            garage_count: 0,
+            capacity: 0,
+            vehicle_count: 0,
+            wishlist_count: wishlist_counts.get(&profile_id).copied().unwrap_or(0),
+            total_value: 0,
+        };
+        for (_, num, vehicle_list) in garages.iter().filter(|(id, _, _)| *id == Some(profile_id)) {
+            entry.garage_count += 1;
+            entry.capacity += num.unwrap_or(0) as i64;
+            let slots = parse_vehicle_list(vehicle_list.as_deref()).unwrap_or_default();
+            for vehicle_id in slots.iter().filter_map(slot_vehicle_id) {
+                entry.vehicle_count += 1;
+                entry.total_value += prices.get(&vehicle_id).copied().unwrap_or(0);
+                unique_vehicles.insert(vehicle_id);
+            }
+        }
+        stats.push(entry);
+    }
+
+    Ok(ProfileStatsReport {
+        garage_count: stats.iter().map(|s| s.garage_count).sum(),
+        capacity: stats.iter().map(|s| s.capacity).sum(),
+        vehicle_count: stats.iter().map(|s| s.vehicle_count).sum(),
+        unique_vehicle_count: unique_vehicles.len(),
+        wishlist_count: stats.iter().map(|s| s.wishlist_count).sum(),
+        total_value: stats.iter().map(|s| s.total_value).sum(),
+        profiles: stats,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::user_db;
+
+    fn add_garage(conn: &Connection, name: &str, profile_id: i32) -> i32 {
+        conn.execute(
+            "INSERT INTO garage_overview (garage_name, num, vehicle_list, garage_order, profile_id) VALUES (?1, 2, '[\"1-1\", null]', 1, ?2)",
+            params![name, profile_id],
+        )
+        .unwrap();
+        conn.last_insert_rowid() as i32
+    }
+
+    fn add_wishlist(conn: &Connection, vehicle_id: &str, target_garage_id: Option<i32>, profile_id: i32) {
+        conn.execute(
+            "INSERT INTO wishlist (vehicle_id, target_garage_id, profile_id) VALUES (?1, ?2, ?3)",
+            params![vehicle_id, target_garage_id, profile_id],
+        )
+        .unwrap();
+    }
+
+    fn wishlist_targets(conn: &Connection, profile_id: i32) -> Vec<(String, Option<i32>)> {
+        let mut stmt = conn
+            .prepare("SELECT vehicle_id, target_garage_id FROM wishlist WHERE profile_id = ?1 ORDER BY id")
+            .unwrap();
+        let rows = stmt.query_map(params![profile_id], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        rows.collect::<Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn copy_profile_remaps_wishlist_targets_to_copied_garages() {
+        let conn = user_db();
+        let garage_a = add_garage(&conn, "A", 1);
+        let garage_b = add_garage(&conn, "B", 1);
+        add_wishlist(&conn, "1-1", Some(garage_b), 1);
+        add_wishlist(&conn, "1-2", None, 1);
+        add_wishlist(&conn, "1-3", Some(garage_a), 1);
+
+        let new_id = copy_profile(&conn, 1, "副本", None).unwrap();
+
+        let copied: HashMap<String, i32> = {
+            let mut stmt = conn.prepare("SELECT garage_name, id FROM garage_overview WHERE profile_id = ?1").unwrap();
+            let rows = stmt.query_map(params![new_id], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+            rows.collect::<Result<_>>().unwrap()
+        };
+        assert_eq!(copied.len(), 2);
+        assert!(!copied.values().any(|id| *id == garage_a || *id == garage_b));
+        assert_eq!(
+            wishlist_targets(&conn, new_id),
+            vec![
+                ("1-1".to_string(), Some(copied["B"])),
+                ("1-2".to_string(), None),
+                ("1-3".to_string(), Some(copied["A"])),
+            ]
+        );
+        // 原角色的数据保持不变
+        assert_eq!(wishlist_targets(&conn, 1)[0], ("1-1".to_string(), Some(garage_b)));
+    }
+
+    #[test]
+    fn copy_profile_clears_targets_outside_source_profile() {
+        let conn = user_db();
+        let other_profile = insert_profile(&conn, "其他", None).unwrap();
+        let other_garage = add_garage(&conn, "其他车库", other_profile);
+        add_wishlist(&conn, "1-1", Some(other_garage), 1);
+
+        let new_id = copy_profile(&conn, 1, "副本", None).unwrap();
+        assert_eq!(wishlist_targets(&conn, new_id), vec![("1-1".to_string(), None)]);
+    }
+
+    #[test]
+    fn copy_profile_copies_conversions_to_copied_garages() {
+        let conn = user_db();
+        let garage = add_garage(&conn, "A", 1);
+        conn.execute(
+            "INSERT INTO vehicle_conversion (profile_id, garage_id, slot, from_vehicle_id, to_vehicle_id, upgrade_cost)
+             VALUES (1, ?1, 0, '1-1', '1-2', 1000)",
+            params![garage],
+        )
+        .unwrap();
+
+        let new_id = copy_profile(&conn, 1, "副本", None).unwrap();
+        let new_garage: i32 = conn
+            .query_row("SELECT id FROM garage_overview WHERE profile_id = ?1", params![new_id], |row| row.get(0))
+            .unwrap();
+        let copied: (i32, String, Option<i64>) = conn
+            .query_row(
+                "SELECT garage_id, to_vehicle_id, upgrade_cost FROM vehicle_conversion WHERE profile_id = ?1",
+                params![new_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(copied, (new_garage, "1-2".to_string(), Some(1000)));
+    }
+}
@@ -0,0 +1,85 @@
+// 导入必要的依赖
+use std::sync::Mutex;
+
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::models::{ApiResponse, ShareServerStatus};
+use crate::share_server::ShareServer;
+
+// 默认只监听本机，局域网共享时由前端传入如0.0.0.0:47800的地址
+const DEFAULT_SHARE_ADDRESS: &str = "127.0.0.1:47800";
+
+// 当前运行的共享服务，同一时间只允许一个
+static SHARE_SERVER: Mutex<Option<ShareServer>> = Mutex::new(None);
+
+fn server_status(server: &Option<ShareServer>) -> ShareServerStatus {
+    ShareServerStatus {
+        running: server.is_some(),
+        address: server.as_ref().map(|server| server.local_addr().to_string()),
+    }
+}
+
+// 启动局域网只读共享服务，访问时需携带共享令牌
+#[command]
+pub fn start_share_server(
+    app: AppHandle,
+    bind_address: Option<String>,
+    token: String,
+) -> Result<ApiResponse<ShareServerStatus>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let mut server = SHARE_SERVER.lock().map_err(|e| e.to_string())?;
+    if server.is_some() {
+        return Ok(ApiResponse {
+            success: false,
+            data: Some(server_status(&server)),
+            error: Some("共享服务已在运行".to_string()),
+        });
+    }
+
+    let bind_address = bind_address.unwrap_or_else(|| DEFAULT_SHARE_ADDRESS.to_string());
+    match ShareServer::start(app_dir, &bind_address, token) {
+        Ok(started) => {
+            *server = Some(started);
+            Ok(ApiResponse {
+                success: true,
+                data: Some(server_status(&server)),
+                error: None,
+            })
+        }
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 停止共享服务
+#[command]
+pub fn stop_share_server() -> Result<ApiResponse<ShareServerStatus>, String> {
+    let mut server = SHARE_SERVER.lock().map_err(|e| e.to_string())?;
+    if let Some(running) = server.take() {
+        running.stop();
+    }
+    Ok(ApiResponse {
+        success: true,
+        data: Some(server_status(&server)),
+        error: None,
+    })
+}
+
+// 获取共享服务状态
+#[command]
+pub fn get_share_server_status() -> Result<ApiResponse<ShareServerStatus>, String> {
+    let server = SHARE_SERVER.lock().map_err(|e| e.to_string())?;
+    Ok(ApiResponse {
+        success: true,
+        data: Some(server_status(&server)),
+        error: None,
+    })
+}
@@ -0,0 +1,232 @@
+// 导入必要的依赖
+use rusqlite::{params, Connection};
+use serde_json::{json, Map, Value};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::garage_api::{is_empty_slot, parse_vehicle_list};
+use crate::models::{ApiResponse, SlotInstance};
+
+// GTA车牌最多8个字符
+const MAX_PLATE_LEN: usize = 8;
+
+// 读取车位中的个性化信息，车牌和备注使用车位已有的plate、remarks字段
+pub(crate) fn slot_instance(slot: &Value) -> SlotInstance {
+    let text = |key: &str| {
+        slot.get(key)
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+    };
+    SlotInstance {
+        plate: text("plate"),
+        notes: text("remarks"),
+        intentional_duplicate: slot.get("intentional_duplicate").and_then(|flag| flag.as_bool()),
+        ..slot
+            .get("instance")
+            .and_then(|instance| serde_json::from_value(instance.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+// 将个性化信息写入车位：车牌、备注和有意重复标记写入plate、remarks、intentional_duplicate字段，
+// 其余字段保存在instance中
+fn set_slot_instance(vehicle: &mut Map<String, Value>, instance: &SlotInstance) -> Result<(), String> {
+    match &instance.plate {
+        Some(plate) => vehicle.insert("plate".to_string(), json!(plate)),
+        None => vehicle.remove("plate"),
+    };
+    vehicle.insert("remarks".to_string(), json!(instance.notes.clone().unwrap_or_default()));
+    // 重复检查（duplicate_api）读取该字段
+    match instance.intentional_duplicate {
+        Some(true) => vehicle.insert("intentional_duplicate".to_string(), json!(true)),
+        _ => vehicle.remove("intentional_duplicate"),
+    };
+
+    let nested = SlotInstance {
+        plate: None,
+        notes: None,
+        intentional_duplicate: None,
+        ..instance.clone()
+    };
+    if nested == SlotInstance::default() {
+        vehicle.remove("instance");
+        return Ok(());
+    }
+    let mut value = serde_json::to_value(&nested).map_err(|e| e.to_string())?;
+    if let Value::Object(fields) = &mut value {
+        fields.remove("plate");
+        fields.remove("notes");
+        fields.remove("intentional_duplicate");
+    }
+    vehicle.insert("instance".to_string(), value);
+    Ok(())
+}
+
+// 校验个性化信息
+fn validate_instance(instance: &SlotInstance) -> Result<(), String> {
+    if let Some(plate) = &instance.plate {
+        if plate.chars().count() > MAX_PLATE_LEN {
+            return Err(format!("车牌不能超过{}个字符", MAX_PLATE_LEN));
+        }
+        if !plate.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ') {
+            return Err("车牌只能包含英文字母、数字和空格".to_string());
+        }
+    }
+    if let Some(price) = instance.purchase_price {
+        if price < 0 {
+            return Err("购买价格不能为负数".to_string());
+        }
+    }
+    Ok(())
+}
+
+// 读取车库中指定车位的载具
+fn load_slot(conn: &Connection, garage_id: i32, slot: usize) -> Result<Vec<Value>, String> {
+    let vehicle_list: Option<String> = conn
+        .query_row(
+            "SELECT vehicle_list FROM garage_overview WHERE id = ?1",
+            params![garage_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let slots = parse_vehicle_list(vehicle_list.as_deref())?;
+    match slots.get(slot) {
+        Some(vehicle) if !is_empty_slot(vehicle) => Ok(slots),
+        _ => Err("该车位没有载具".to_string()),
+    }
+}
+
+// 获取车位中载具的个性化信息
+#[command]
+pub fn get_slot_instance(
+    app: AppHandle,
+    garage_id: i32,
+    slot: usize,
+) -> Result<ApiResponse<SlotInstance>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_user.db");
+
+    match Connection::open(&db_path) {
+        Ok(conn) => match load_slot(&conn, garage_id, slot) {
+            Ok(slots) => Ok(ApiResponse {
+                success: true,
+                data: Some(slot_instance(&slots[slot])),
+                error: None,
+            }),
+            Err(e) => Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            }),
+        },
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 更新车位中载具的个性化信息，所有字段为空时清除
+#[command]
+pub fn update_slot_instance(
+    app: AppHandle,
+    garage_id: i32,
+    slot: usize,
+    instance: SlotInstance,
+) -> Result<ApiResponse<SlotInstance>, String> {
+    if let Err(e) = validate_instance(&instance) {
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        });
+    }
+
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_user.db");
+
+    match Connection::open(&db_path) {
+        Ok(mut conn) => {
+            match conn.transaction() {
+                Ok(tx) => {
+                    let mut slots = match load_slot(&tx, garage_id, slot) {
+                        Ok(result) => result,
+                        Err(e) => {
+                            return Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e),
+                            })
+                        }
+                    };
+
+                    if let Value::Object(vehicle) = &mut slots[slot] {
+                        if let Err(e) = set_slot_instance(vehicle, &instance) {
+                            return Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e),
+                            });
+                        }
+                    }
+
+                    let vehicle_list = match serde_json::to_string(&slots) {
+                        Ok(vehicle_list) => vehicle_list,
+                        Err(e) => {
+                            return Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e.to_string()),
+                            })
+                        }
+                    };
+
+                    match tx.execute(
+                        "UPDATE garage_overview SET vehicle_list = ?1, version = version + 1 WHERE id = ?2",
+                        params![vehicle_list, garage_id],
+                    ) {
+                        Ok(_) => match tx.commit() {
+                            Ok(_) => Ok(ApiResponse {
+                                success: true,
+                                data: Some(instance),
+                                error: None,
+                            }),
+                            Err(e) => Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e.to_string()),
+                            }),
+                        },
+                        Err(e) => {
+                            // 回滚事务
+                            let _ = tx.rollback();
+                            Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e.to_string()),
+                            })
+                        }
+                    }
+                }
+                Err(e) => Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
@@ -0,0 +1,402 @@
+// 导入必要的依赖
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::database::create_vehicle_stats_table;
+use crate::models::{
+    ApiResponse, CsvRowError, NormalizedVehicleStats, StatsImportReport, VehicleStats, VehicleStatsComparison,
+};
+
+// 性能数据表的查询字段
+const STATS_COLUMNS: &str =
+    "vehicle_id, speed, acceleration, braking, handling, lap_time, top_speed, seats, drive_type";
+
+fn map_vehicle_stats(row: &rusqlite::Row) -> Result<VehicleStats> {
+    Ok(VehicleStats {
+        vehicle_id: row.get(0)?,
+        speed: row.get(1)?,
+        acceleration: row.get(2)?,
+        braking: row.get(3)?,
+        handling: row.get(4)?,
+        lap_time: row.get(5)?,
+        top_speed: row.get(6)?,
+        seats: row.get(7)?,
+        drive_type: row.get(8)?,
+    })
+}
+
+// 读取所有载具的性能数据
+pub(crate) fn load_vehicle_stats(conn: &Connection) -> Result<Vec<VehicleStats>> {
+    create_vehicle_stats_table(conn)?;
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM vehicle_stats ORDER BY vehicle_id", STATS_COLUMNS))?;
+    let stats_iter = stmt.query_map([], map_vehicle_stats)?;
+    stats_iter.collect()
+}
+
+// 获取单个载具的性能数据
+#[command]
+pub fn get_vehicle_stats(app: AppHandle, vehicle_id: String) -> Result<ApiResponse<VehicleStats>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| {
+        create_vehicle_stats_table(&conn)?;
+        conn.query_row(
+            &format!("SELECT {} FROM vehicle_stats WHERE vehicle_id = ?1", STATS_COLUMNS),
+            params![vehicle_id],
+            map_vehicle_stats,
+        )
+        .optional()
+    });
+
+    match result {
+        Ok(Some(stats)) => Ok(ApiResponse {
+            success: true,
+            data: Some(stats),
+            error: None,
+        }),
+        Ok(None) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("No data found".to_string()),
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 解析一行CSV，支持双引号包裹的字段和""转义，不支持字段内换行
+fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|field| field.trim().to_string()).collect()
+}
+
+// 性能数据均为非负的有限数值，NaN和无穷大会破坏对比时的归一化
+fn parse_number(column: &str, value: &str) -> Result<Option<f64>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<f64>() {
+        Ok(number) if number.is_finite() && number >= 0.0 => Ok(Some(number)),
+        _ => Err(format!("{}不是有效的数字: {}", column, value)),
+    }
+}
+
+// 圈速支持秒数（62.5）或分:秒格式（1:02.5）
+fn parse_lap_time(value: &str) -> Result<Option<f64>, String> {
+    match value.split_once(':') {
+        Some((minutes, seconds)) => {
+            let minutes = parse_number("lap_time", minutes.trim());
+            let seconds = parse_number("lap_time", seconds.trim());
+            match (minutes, seconds) {
+                (Ok(Some(minutes)), Ok(Some(seconds))) => Ok(Some(minutes * 60.0 + seconds)),
+                _ => Err(format!("lap_time不是有效的圈速: {}", value)),
+            }
+        }
+        None => parse_number("lap_time", value),
+    }
+}
+
+// 将CSV中的一行转换为性能数据，vehicle_id为空时按英文名称查找载具
+fn parse_stats_row(
+    headers: &[String],
+    fields: &[String],
+    ids: &HashMap<String, String>,
+    names: &HashMap<String, Option<String>>,
+) -> Result<VehicleStats, String> {
+    let get = |column: &str| -> &str {
+        headers
+            .iter()
+            .position(|header| header == column)
+            .and_then(|index| fields.get(index))
+            .map(|field| field.as_str())
+            .unwrap_or("")
+    };
+
+    let vehicle_id = match (get("vehicle_id"), get("vehicle_name_en")) {
+        ("", "") => return Err("缺少vehicle_id或vehicle_name_en".to_string()),
+        ("", name) => match names.get(&name.to_lowercase()) {
+            Some(Some(vehicle_id)) => vehicle_id.clone(),
+            Some(None) => return Err(format!("有多个载具的英文名称为{}，请使用vehicle_id", name)),
+            None => return Err(format!("找不到英文名称为{}的载具", name)),
+        },
+        (id, _) => ids.get(id).cloned().ok_or_else(|| format!("载具{}不存在", id))?,
+    };
+    let seats = match get("seats") {
+        "" => None,
+        value => Some(value.parse::<i32>().map_err(|_| format!("seats不是有效的整数: {}", value))?),
+    };
+    let drive_type = match get("drive_type") {
+        "" => None,
+        value => Some(value.to_uppercase()),
+    };
+
+    Ok(VehicleStats {
+        vehicle_id,
+        speed: parse_number("speed", get("speed"))?,
+        acceleration: parse_number("acceleration", get("acceleration"))?,
+        braking: parse_number("braking", get("braking"))?,
+        handling: parse_number("handling", get("handling"))?,
+        lap_time: parse_lap_time(get("lap_time"))?,
+        top_speed: parse_number("top_speed", get("top_speed"))?,
+        seats,
+        drive_type,
+    })
+}
+
+// 从CSV导入性能数据，CSV中为空的字段保留原有数据
+fn run_import_vehicle_stats(conn: &mut Connection, content: &str) -> Result<StatsImportReport, String> {
+    let mut lines = content.trim_start_matches('\u{feff}').lines();
+    let headers: Vec<String> = match lines.next() {
+        Some(header) => parse_csv_line(header).into_iter().map(|h| h.to_lowercase()).collect(),
+        None => return Err("CSV文件为空".to_string()),
+    };
+    if !headers.iter().any(|h| h == "vehicle_id" || h == "vehicle_name_en") {
+        return Err("CSV表头缺少vehicle_id或vehicle_name_en".to_string());
+    }
+
+    let vehicles = load_vehicle_overviews(conn).map_err(|e| e.to_string())?;
+    let ids: HashMap<String, String> = vehicles.iter().map(|v| (v.id.clone(), v.id.clone())).collect();
+    // 英文名称到载具ID，名称重复时为空
+    let mut names: HashMap<String, Option<String>> = HashMap::new();
+    for vehicle in &vehicles {
+        if let Some(name) = vehicle.vehicle_name_en.as_ref() {
+            names
+                .entry(name.to_lowercase())
+                .and_modify(|id| *id = None)
+                .or_insert_with(|| Some(vehicle.id.clone()));
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    create_vehicle_stats_table(&tx).map_err(|e| e.to_string())?;
+    let mut imported = 0;
+    let mut skipped = Vec::new();
+    for (index, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let stats = match parse_stats_row(&headers, &parse_csv_line(line), &ids, &names) {
+            Ok(stats) => stats,
+            Err(error) => {
+                skipped.push(CsvRowError { line: index + 2, error });
+                continue;
+            }
+        };
+        tx.execute(
+            "INSERT INTO vehicle_stats (vehicle_id, speed, acceleration, braking, handling, lap_time, top_speed, seats, drive_type)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(vehicle_id) DO UPDATE SET
+                speed = COALESCE(excluded.speed, speed),
+                acceleration = COALESCE(excluded.acceleration, acceleration),
+                braking = COALESCE(excluded.braking, braking),
+                handling = COALESCE(excluded.handling, handling),
+                lap_time = COALESCE(excluded.lap_time, lap_time),
+                top_speed = COALESCE(excluded.top_speed, top_speed),
+                seats = COALESCE(excluded.seats, seats),
+                drive_type = COALESCE(excluded.drive_type, drive_type)",
+            params![
+                stats.vehicle_id,
+                stats.speed,
+                stats.acceleration,
+                stats.braking,
+                stats.handling,
+                stats.lap_time,
+                stats.top_speed,
+                stats.seats,
+                stats.drive_type,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        imported += 1;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(StatsImportReport { imported, skipped })
+}
+
+// 从本地CSV文件导入载具性能数据
+// 表头需包含vehicle_id或vehicle_name_en，其余列为speed、acceleration、braking、handling、lap_time、top_speed、seats、drive_type
+#[command]
+pub fn import_vehicle_stats_csv(app: AppHandle, path: String) -> Result<ApiResponse<StatsImportReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = fs::read_to_string(Path::new(&path))
+        .map_err(|e| format!("读取CSV文件失败: {}", e))
+        .and_then(|content| {
+            let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+            run_import_vehicle_stats(&mut conn, &content)
+        });
+
+    match result {
+        Ok(report) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 目录中某项数据的取值范围
+fn value_range(stats: &[VehicleStats], value: fn(&VehicleStats) -> Option<f64>) -> Option<(f64, f64)> {
+    stats.iter().filter_map(value).fold(None, |range, v| match range {
+        Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+        None => Some((v, v)),
+    })
+}
+
+// 按取值范围换算到0-100，lower_is_better时取反；范围为单一值时记为100
+fn normalize(value: Option<f64>, range: Option<(f64, f64)>, lower_is_better: bool) -> Option<f64> {
+    let (value, (min, max)) = (value?, range?);
+    if max <= min {
+        return Some(100.0);
+    }
+    let ratio = (value - min) / (max - min);
+    let ratio = if lower_is_better { 1.0 - ratio } else { ratio };
+    Some((ratio * 1000.0).round() / 10.0)
+}
+
+fn run_compare_vehicles(conn: &Connection, ids: &[String]) -> Result<Vec<VehicleStatsComparison>, String> {
+    let vehicles: HashMap<String, _> = load_vehicle_overviews(conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|v| (v.id.clone(), v))
+        .collect();
+    let all_stats = load_vehicle_stats(conn).map_err(|e| e.to_string())?;
+
+    // 以整个目录为基准归一化，使同一载具在不同对比中的得分一致
+    let speed = value_range(&all_stats, |s| s.speed);
+    let acceleration = value_range(&all_stats, |s| s.acceleration);
+    let braking = value_range(&all_stats, |s| s.braking);
+    let handling = value_range(&all_stats, |s| s.handling);
+    let lap_time = value_range(&all_stats, |s| s.lap_time);
+    let top_speed = value_range(&all_stats, |s| s.top_speed);
+
+    ids.iter()
+        .map(|id| {
+            let vehicle = vehicles.get(id).ok_or_else(|| format!("载具{}不存在", id))?;
+            let stats = all_stats.iter().find(|s| &s.vehicle_id == id).cloned();
+            let normalized = stats.as_ref().map(|s| NormalizedVehicleStats {
+                speed: normalize(s.speed, speed, false),
+                acceleration: normalize(s.acceleration, acceleration, false),
+                braking: normalize(s.braking, braking, false),
+                handling: normalize(s.handling, handling, false),
+                lap_time: normalize(s.lap_time, lap_time, true),
+                top_speed: normalize(s.top_speed, top_speed, false),
+            });
+            Ok(VehicleStatsComparison {
+                vehicle_id: id.clone(),
+                vehicle_name: vehicle.vehicle_name.clone(),
+                vehicle_name_en: vehicle.vehicle_name_en.clone(),
+                vehicle_type: vehicle.vehicle_type.clone(),
+                stats,
+                normalized,
+            })
+        })
+        .collect()
+}
+
+// 并排对比多个载具的性能数据，按传入顺序返回
+#[command]
+pub fn compare_vehicles(app: AppHandle, ids: Vec<String>) -> Result<ApiResponse<Vec<VehicleStatsComparison>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db"))
+        .map_err(|e| e.to_string())
+        .and_then(|conn| run_compare_vehicles(&conn, &ids));
+
+    match result {
+        Ok(comparison) => Ok(ApiResponse {
+            success: true,
+            data: Some(comparison),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_line_splits_and_trims_fields() {
+        assert_eq!(parse_csv_line("a, b ,c"), vec!["a", "b", "c"]);
+        assert_eq!(parse_csv_line("a,,"), vec!["a", "", ""]);
+        assert_eq!(parse_csv_line(""), vec![""]);
+    }
+
+    #[test]
+    fn csv_line_handles_quotes() {
+        assert_eq!(parse_csv_line(r#""Pegassi, Zentorno",1"#), vec!["Pegassi, Zentorno", "1"]);
+        assert_eq!(parse_csv_line(r#""say ""hi""",2"#), vec![r#"say "hi""#, "2"]);
+        assert_eq!(parse_csv_line(r#""""#), vec![""]);
+    }
+
+    #[test]
+    fn lap_time_accepts_seconds_and_minutes() {
+        assert_eq!(parse_lap_time("").unwrap(), None);
+        assert_eq!(parse_lap_time("62.5").unwrap(), Some(62.5));
+        assert_eq!(parse_lap_time("1:02.5").unwrap(), Some(62.5));
+        assert_eq!(parse_lap_time("0:59").unwrap(), Some(59.0));
+        assert_eq!(parse_lap_time(" 2 : 00 ").unwrap(), Some(120.0));
+    }
+
+    #[test]
+    fn lap_time_rejects_invalid_values() {
+        for value in ["abc", "1:", ":30", "1:2:3", "NaN", "inf", "-1", "-1:30", "1:-5", "1:NaN", "infinity:1"] {
+            assert!(parse_lap_time(value).is_err(), "{} should be rejected", value);
+        }
+    }
+
+    #[test]
+    fn number_rejects_non_finite_and_negative_values() {
+        assert_eq!(parse_number("top_speed", "").unwrap(), None);
+        assert_eq!(parse_number("top_speed", "0").unwrap(), Some(0.0));
+        assert_eq!(parse_number("top_speed", "120.5").unwrap(), Some(120.5));
+        for value in ["NaN", "inf", "-inf", "-0.1", "1e400", "fast"] {
+            assert!(parse_number("top_speed", value).is_err(), "{} should be rejected", value);
+        }
+    }
+}
@@ -0,0 +1,415 @@
+// 导入必要的依赖
+use std::collections::HashSet;
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+use serde_json::Value;
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::brand_api::load_vehicle_brands;
+use crate::api::garage_api::{attach_user_db, is_empty_slot, parse_vehicle_list, slot_vehicle_id, vehicle_slot};
+use crate::api::vehicle_api::{check_version, load_vehicle_by_id};
+use crate::database::{create_vehicle_variant_table, ACTIVE_PROFILE};
+use crate::models::{ApiResponse, VehicleConversion, VehicleVariant};
+
+// 读取改装关系，vehicle_id不为空时只返回以该载具为基础或改装结果的关系
+fn load_vehicle_variants(conn: &Connection, vehicle_id: Option<&str>) -> Result<Vec<VehicleVariant>> {
+    create_vehicle_variant_table(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT r.base_id, r.variant_id, r.upgrade_kind, r.upgrade_cost, b.vehicle_name, v.vehicle_name
+         FROM vehicle_variant r
+         LEFT JOIN vehicle_overview b ON b.id = r.base_id
+         LEFT JOIN vehicle_overview v ON v.id = r.variant_id
+         WHERE ?1 IS NULL OR r.base_id = ?1 OR r.variant_id = ?1
+         ORDER BY r.base_id, r.variant_id",
+    )?;
+    let variant_iter = stmt.query_map(params![vehicle_id], |row| {
+        Ok(VehicleVariant {
+            base_id: row.get(0)?,
+            variant_id: row.get(1)?,
+            upgrade_kind: row.get(2)?,
+            upgrade_cost: row.get(3)?,
+            base_name: row.get(4)?,
+            variant_name: row.get(5)?,
+        })
+    })?;
+    variant_iter.collect()
+}
+
+// 获取改装关系
+#[command]
+pub fn get_vehicle_variants(
+    app: AppHandle,
+    vehicle_id: Option<String>,
+) -> Result<ApiResponse<Vec<VehicleVariant>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match Connection::open(app_dir.join("gtavm_common.db"))
+        .and_then(|conn| load_vehicle_variants(&conn, vehicle_id.as_deref()))
+    {
+        Ok(variants) => Ok(ApiResponse {
+            success: true,
+            data: Some(variants),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 校验改装关系：两端载具必须存在且不同，不能与已有关系形成循环（包括方向相反的关系）
+fn validate_variant(conn: &Connection, variant: &VehicleVariant) -> Result<(), String> {
+    if variant.base_id == variant.variant_id {
+        return Err("改装后的载具不能与基础载具相同".to_string());
+    }
+    for id in [&variant.base_id, &variant.variant_id] {
+        if load_vehicle_by_id(conn, id).map_err(|e| e.to_string())?.is_none() {
+            return Err(format!("载具{}不存在", id));
+        }
+    }
+    if variant.upgrade_cost.map(|cost| cost < 0).unwrap_or(false) {
+        return Err("改装费用不能为负数".to_string());
+    }
+
+    // 从改装后的载具出发沿已有关系查找，能到达基础载具时新关系会形成循环
+    let edges: Vec<(String, String)> = {
+        let mut stmt = conn
+            .prepare("SELECT base_id, variant_id FROM vehicle_variant")
+            .map_err(|e| e.to_string())?;
+        let edge_iter = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        edge_iter.collect::<Result<_>>().map_err(|e| e.to_string())?
+    };
+    let mut visited = HashSet::new();
+    let mut pending = vec![variant.variant_id.as_str()];
+    while let Some(current) = pending.pop() {
+        if current == variant.base_id {
+            return Err(format!(
+                "{}已可改装为{}，不能形成循环的改装关系",
+                variant.variant_id, variant.base_id
+            ));
+        }
+        if visited.insert(current) {
+            pending.extend(edges.iter().filter(|(base, _)| base == current).map(|(_, next)| next.as_str()));
+        }
+    }
+    Ok(())
+}
+
+// 保存改装关系，已存在时更新改装类型和费用
+#[command]
+pub fn save_vehicle_variant(app: AppHandle, variant: VehicleVariant) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db"))
+        .map_err(|e| e.to_string())
+        .and_then(|conn| {
+            create_vehicle_variant_table(&conn).map_err(|e| e.to_string())?;
+            validate_variant(&conn, &variant)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO vehicle_variant (base_id, variant_id, upgrade_kind, upgrade_cost)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![variant.base_id, variant.variant_id, variant.upgrade_kind, variant.upgrade_cost],
+            )
+            .map_err(|e| e.to_string())
+        });
+
+    match result {
+        Ok(_) => Ok(ApiResponse {
+            success: true,
+            data: None,
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 删除改装关系
+#[command]
+pub fn delete_vehicle_variant(app: AppHandle, base_id: String, variant_id: String) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| {
+        create_vehicle_variant_table(&conn)?;
+        conn.execute(
+            "DELETE FROM vehicle_variant WHERE base_id = ?1 AND variant_id = ?2",
+            params![base_id, variant_id],
+        )
+    });
+
+    match result {
+        Ok(_) => Ok(ApiResponse {
+            success: true,
+            data: None,
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 将车位中的载具改装为指定的改装载具，车位中的备注和个性化信息保持不变
+// 提供expected_version时校验车库当前版本号，不一致时拒绝改装
+#[command]
+pub fn convert_slot_vehicle(
+    app: AppHandle,
+    garage_id: i32,
+    slot: usize,
+    variant_id: String,
+    expected_version: Option<i64>,
+) -> Result<ApiResponse<VehicleConversion>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_convert_slot_vehicle(&app_dir, garage_id, slot, &variant_id, expected_version) {
+        Ok(conversion) => Ok(ApiResponse {
+            success: true,
+            data: Some(conversion),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_convert_slot_vehicle(
+    app_dir: &Path,
+    garage_id: i32,
+    slot: usize,
+    variant_id: &str,
+    expected_version: Option<i64>,
+) -> Result<VehicleConversion, String> {
+    let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    create_vehicle_variant_table(&conn).map_err(|e| e.to_string())?;
+    attach_user_db(&conn, app_dir).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let conversion = convert_slot_in(&tx, garage_id, slot, variant_id, expected_version)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(conversion)
+}
+
+// 在已附加用户数据库（user_db）的事务中改装车位载具并记录改装
+fn convert_slot_in(
+    tx: &Connection,
+    garage_id: i32,
+    slot: usize,
+    variant_id: &str,
+    expected_version: Option<i64>,
+) -> Result<VehicleConversion, String> {
+    let (profile_id, vehicle_list, version): (Option<i32>, Option<String>, Option<i64>) = tx
+        .query_row(
+            "SELECT profile_id, vehicle_list, version FROM user_db.garage_overview WHERE id = ?1",
+            params![garage_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "车库不存在".to_string())?;
+    if let Some(expected_version) = expected_version {
+        check_version(&garage_id.to_string(), version, expected_version)?;
+    }
+    let mut slots = parse_vehicle_list(vehicle_list.as_deref())?;
+    let from_id = match slots.get(slot) {
+        Some(current) if !is_empty_slot(current) => slot_vehicle_id(current).ok_or_else(|| "该车位没有载具".to_string())?,
+        _ => return Err("该车位没有载具".to_string()),
+    };
+
+    let (upgrade_kind, upgrade_cost): (Option<String>, Option<i64>) = tx
+        .query_row(
+            "SELECT upgrade_kind, upgrade_cost FROM vehicle_variant WHERE base_id = ?1 AND variant_id = ?2",
+            params![from_id, variant_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("载具{}不能改装为{}", from_id, variant_id))?;
+
+    let variant = load_vehicle_by_id(tx, variant_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("载具{}不存在", variant_id))?;
+    let brands = load_vehicle_brands(tx).map_err(|e| e.to_string())?;
+    let brand = variant.brand_id.and_then(|brand_id| brands.iter().find(|b| b.id == brand_id));
+
+    // 改装后仍是同一辆车，保留原车位中载具快照以外的字段
+    let mut converted = vehicle_slot(&variant, brand);
+    if let (Value::Object(converted), Value::Object(previous)) = (&mut converted, &slots[slot]) {
+        for (key, value) in previous {
+            if key == "remarks" || !converted.contains_key(key) {
+                converted.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    slots[slot] = converted;
+    let vehicle_list = serde_json::to_string(&slots).map_err(|e| e.to_string())?;
+
+    let garage_version: i64 = tx
+        .query_row(
+            "UPDATE user_db.garage_overview SET vehicle_list = ?1, version = version + 1 WHERE id = ?2 RETURNING version",
+            params![vehicle_list, garage_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO user_db.vehicle_conversion
+            (profile_id, garage_id, slot, from_vehicle_id, to_vehicle_id, upgrade_kind, upgrade_cost, converted_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, date('now'))",
+        params![profile_id, garage_id, slot as i64, from_id, variant_id, upgrade_kind, upgrade_cost],
+    )
+    .map_err(|e| e.to_string())?;
+    let id = tx.last_insert_rowid() as i32;
+    let converted_at: Option<String> = tx
+        .query_row("SELECT converted_at FROM user_db.vehicle_conversion WHERE id = ?1", params![id], |row| {
+            row.get(0)
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(VehicleConversion {
+        id,
+        garage_id,
+        slot,
+        from_vehicle_id: from_id,
+        to_vehicle_id: variant_id.to_string(),
+        upgrade_kind,
+        upgrade_cost,
+        converted_at,
+        garage_version: Some(garage_version),
+    })
+}
+
+// 获取当前角色的改装记录（按时间倒序）
+#[command]
+pub fn get_vehicle_conversions(app: AppHandle) -> Result<ApiResponse<Vec<VehicleConversion>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let result = Connection::open(app_dir.join("gtavm_user.db")).and_then(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, garage_id, slot, from_vehicle_id, to_vehicle_id, upgrade_kind, upgrade_cost, converted_at
+             FROM vehicle_conversion WHERE profile_id = {} ORDER BY id DESC",
+            ACTIVE_PROFILE
+        ))?;
+        let conversion_iter = stmt.query_map([], |row| {
+            Ok(VehicleConversion {
+                id: row.get(0)?,
+                garage_id: row.get(1)?,
+                slot: row.get::<_, i64>(2)? as usize,
+                from_vehicle_id: row.get(3)?,
+                to_vehicle_id: row.get(4)?,
+                upgrade_kind: row.get(5)?,
+                upgrade_cost: row.get(6)?,
+                converted_at: row.get(7)?,
+                garage_version: None,
+            })
+        })?;
+        conversion_iter.collect::<Result<Vec<_>>>()
+    });
+
+    match result {
+        Ok(conversions) => Ok(ApiResponse {
+            success: true,
+            data: Some(conversions),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::vehicle_api::VERSION_CONFLICT;
+    use crate::database::test_support::catalog_db;
+
+    // 车库第0个车位为1-1（带备注），第1个车位为空
+    fn setup() -> (Connection, i32) {
+        let conn = catalog_db();
+        conn.execute_batch(
+            "INSERT INTO vehicle_brand (id, brand_name, brand_name_en) VALUES (1, '佩嘉西', 'Pegassi');
+             INSERT INTO vehicle_overview (id, brand_id, vehicle_name) VALUES ('1-1', 1, '捷豹'), ('1-2', 1, '捷豹改');
+             INSERT INTO vehicle_variant (base_id, variant_id, upgrade_kind, upgrade_cost) VALUES ('1-1', '1-2', 'benny', 800);",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO user_db.garage_overview (garage_name, num, vehicle_list, profile_id, version) VALUES ('车库', 2, ?1, 1, 3)",
+            params![r#"[{"id": "1-1", "vehicleName": "捷豹", "remarks": "黑色"}, null]"#],
+        )
+        .unwrap();
+        let garage_id = conn.last_insert_rowid() as i32;
+        (conn, garage_id)
+    }
+
+    fn slot_value(conn: &Connection, garage_id: i32, slot: usize) -> Value {
+        let list: Option<String> = conn
+            .query_row("SELECT vehicle_list FROM user_db.garage_overview WHERE id = ?1", params![garage_id], |row| row.get(0))
+            .unwrap();
+        parse_vehicle_list(list.as_deref()).unwrap()[slot].clone()
+    }
+
+    #[test]
+    fn converts_slot_and_records_conversion() {
+        let (conn, garage_id) = setup();
+        let conversion = convert_slot_in(&conn, garage_id, 0, "1-2", Some(3)).unwrap();
+        assert_eq!(conversion.from_vehicle_id, "1-1");
+        assert_eq!(conversion.upgrade_cost, Some(800));
+        assert_eq!(conversion.garage_version, Some(4));
+
+        let slot = slot_value(&conn, garage_id, 0);
+        assert_eq!(slot_vehicle_id(&slot).as_deref(), Some("1-2"));
+        assert_eq!(slot["remarks"], "黑色");
+        let recorded: i64 = conn
+            .query_row("SELECT COUNT(*) FROM user_db.vehicle_conversion WHERE garage_id = ?1", params![garage_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(recorded, 1);
+    }
+
+    #[test]
+    fn version_is_checked_only_when_provided() {
+        let (conn, garage_id) = setup();
+        let err = convert_slot_in(&conn, garage_id, 0, "1-2", Some(2)).unwrap_err();
+        assert!(err.starts_with(VERSION_CONFLICT));
+        assert_eq!(slot_vehicle_id(&slot_value(&conn, garage_id, 0)).as_deref(), Some("1-1"));
+
+        assert!(convert_slot_in(&conn, garage_id, 0, "1-2", None).is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_slots_and_unknown_variants() {
+        let (conn, garage_id) = setup();
+        assert!(convert_slot_in(&conn, garage_id, 1, "1-2", None).is_err());
+        assert!(convert_slot_in(&conn, garage_id, 5, "1-2", None).is_err());
+        assert!(convert_slot_in(&conn, garage_id, 0, "1-3", None).is_err());
+        assert!(convert_slot_in(&conn, garage_id + 1, 0, "1-2", None).is_err());
+    }
+}
@@ -1,73 +1,57 @@
 // 导入必要的依赖
-use rusqlite::{params, Connection, Result};
+use std::collections::HashSet;
+use std::path::Path;
+
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Result};
+use serde_json::json;
 use tauri::{command, AppHandle, Manager};
 
 // 导入数据模型
-use crate::models::{ApiResponse, VehicleOverview, FeatureTypeDict};
+use crate::api::acquisition_api::{acquisition_matches, attach_acquisitions};
+use crate::api::catalog_diff_api::load_dict;
+use crate::api::garage_api::{attach_user_db, rewrite_garage_slots};
+use crate::api::image_api::{remove_image_files, vehicle_image_file};
+use crate::api::integrity_api::parse_vehicle_id;
+use crate::database::{has_column, record_price_changes, sync_dlc_releases};
+use crate::models::{ApiResponse, VehicleOverview, VehicleFilter, VehiclePatch, FeatureTypeDict, RekeyReport};
+
+// 版本冲突错误的前缀，前端据此提示刷新
+pub const VERSION_CONFLICT: &str = "版本冲突";
+
+// 引用载具目录ID的字段名，载具改ID时需同步更新
+const VEHICLE_ID_COLUMNS: [&str; 5] = ["vehicle_id", "base_id", "variant_id", "from_vehicle_id", "to_vehicle_id"];
 
-// 获取所有载具概览
+// 获取所有载具概览，filter可按商店、可获取状态、解锁条件和交易价格筛选
 #[command]
-pub fn get_vehicle_overviews(app: AppHandle) -> Result<ApiResponse<Vec<VehicleOverview>>, String> {
+pub fn get_vehicle_overviews(
+    app: AppHandle,
+    filter: Option<VehicleFilter>,
+) -> Result<ApiResponse<Vec<VehicleOverview>>, String> {
     let app_dir = app
         .path()
         .app_data_dir()
         .expect("Failed to get app data directory");
     let db_path = app_dir.join("gtavm_common.db");
 
-    match Connection::open(&db_path) {
-        Ok(conn) => match conn.prepare("SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks FROM vehicle_overview") {
-            Ok(mut stmt) => {
-                match stmt.query_map([], |row| {
-                    Ok(VehicleOverview {
-                        id: row.get(0)?,
-                        brand_id: row.get(1)?,
-                        vehicle_name: row.get(2)?,
-                        vehicle_name_en: row.get(3)?,
-                        vehicle_type: row.get(4)?,
-                        feature: row.get(5)?,
-                        price: match row.get(6) {
-                            Ok(price_val) => price_val,
-                            Err(_) => {
-                                // 如果无法直接获取为i32，尝试先获取为文本再转换
-                                match row.get::<_, String>(6) {
-                                    Ok(text_price) => {
-                                        match text_price.parse::<i32>() {
-                                            Ok(int_price) => Some(int_price),
-                                            Err(_) => None,
-                                        }
-                                    },
-                                    Err(_) => None,
-                                }
-                            },
-                        },
-                        remarks: row.get(7)?,
-                    })
-                }) {
-                    Ok(vehicle_iter) => match vehicle_iter.collect::<Result<_>>() {
-                        Ok(vehicles) => Ok(ApiResponse {
-                    success: true,
-                    data: Some(vehicles),
-                    error: None,
-                }),
-                        Err(e) => Ok(ApiResponse {
-                            success: false,
-                            data: None,
-                            error: Some(e.to_string()),
-                        }),
-                    },
-                    Err(e) => Ok(ApiResponse {
-                            success: false,
-                            data: None,
-                            error: Some(e.to_string()),
-                        }),
-                }
-            }
-            Err(e) => Ok(ApiResponse {
-                    success: false,
-                    data: None,
-                    error: Some(e.to_string()),
-                }),
-        },
+    let result = Connection::open(&db_path).and_then(|conn| {
+        let mut vehicles = load_vehicle_overviews(&conn)?;
+        attach_acquisitions(&conn, &mut vehicles)?;
+        Ok(vehicles)
+    });
+
+    match result {
+        Ok(vehicles) => Ok(ApiResponse {
+            success: true,
+            data: Some(match filter {
+                Some(filter) => vehicles
+                    .into_iter()
+                    .filter(|vehicle| acquisition_matches(vehicle, &filter))
+                    .collect(),
+                None => vehicles,
+            }),
+            error: None,
+        }),
         Err(e) => Ok(ApiResponse {
             success: false,
             data: None,
@@ -89,7 +73,7 @@ pub fn get_vehicle_overviews_by_brand(
     let db_path = app_dir.join("gtavm_common.db"); // 使用通用数据库文件存储载具数据
 
     match Connection::open(&db_path) {
-        Ok(conn) => match conn.prepare("SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks FROM vehicle_overview WHERE brand_id = ?1") {
+        Ok(conn) => match conn.prepare("SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks, dlc_id, version FROM vehicle_overview WHERE brand_id = ?1") {
             Ok(mut stmt) => {
                 match stmt.query_map(params![brand_id], |row| {
                     Ok(VehicleOverview {
@@ -115,9 +99,15 @@ pub fn get_vehicle_overviews_by_brand(
                             },
                         },
                         remarks: row.get(7)?,
+                        dlc_id: row.get(8)?,
+                        version: row.get(9)?,
+                        acquisition: None,
                     })
                 }) {
-                    Ok(vehicle_iter) => match vehicle_iter.collect::<Result<_>>() {
+                    Ok(vehicle_iter) => match vehicle_iter
+                        .collect::<Result<Vec<_>>>()
+                        .and_then(|mut vehicles| attach_acquisitions(&conn, &mut vehicles).map(|_| vehicles))
+                    {
                         Ok(vehicles) => Ok(ApiResponse {
                             success: true,
                             data: Some(vehicles),
@@ -163,7 +153,7 @@ pub fn get_vehicle_overview_by_id(
     let db_path = app_dir.join("gtavm_common.db");
 
     match Connection::open(&db_path) {
-        Ok(conn) => match conn.prepare("SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks FROM vehicle_overview WHERE id = ?1") {
+        Ok(conn) => match conn.prepare("SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks, dlc_id, version FROM vehicle_overview WHERE id = ?1") {
             Ok(mut stmt) => {
                 match stmt.query_row(params![id], |row| {
                     Ok(VehicleOverview {
@@ -189,13 +179,20 @@ pub fn get_vehicle_overview_by_id(
                             },
                         },
                         remarks: row.get(7)?,
+                        dlc_id: row.get(8)?,
+                        version: row.get(9)?,
+                        acquisition: None,
                     })
                 }) {
-                    Ok(vehicle) => Ok(ApiResponse {
-                        success: true,
-                        data: Some(vehicle),
-                        error: None,
-                    }),
+                    Ok(mut vehicle) => {
+                        // 获取方式为附加信息，读取失败时不影响载具本身
+                        let _ = attach_acquisitions(&conn, std::slice::from_mut(&mut vehicle));
+                        Ok(ApiResponse {
+                            success: true,
+                            data: Some(vehicle),
+                            error: None,
+                        })
+                    }
                     Err(e) => Ok(ApiResponse {
                         success: false,
                         data: None,
@@ -221,7 +218,7 @@ pub fn get_vehicle_overview_by_id(
 #[command]
 pub fn add_vehicle_overview(
     app: AppHandle,
-    vehicle: VehicleOverview,
+    mut vehicle: VehicleOverview,
 ) -> Result<ApiResponse<VehicleOverview>, String> {
     let app_dir = app
         .path()
@@ -253,9 +250,30 @@ pub fn add_vehicle_overview(
                         return Err("指定的品牌不存在".to_string());
                     }
 
+                    // 未指定ID时按品牌分配下一个可用ID
+                    if vehicle.id.trim().is_empty() {
+                        match vehicle.brand_id.map(|brand_id| next_vehicle_id(&tx, brand_id)) {
+                            Some(Ok(id)) => vehicle.id = id,
+                            Some(Err(e)) => {
+                                return Ok(ApiResponse {
+                                    success: false,
+                                    data: None,
+                                    error: Some(e.to_string()),
+                                })
+                            }
+                            None => {
+                                return Ok(ApiResponse {
+                                    success: false,
+                                    data: None,
+                                    error: Some("未指定载具品牌，无法分配ID".to_string()),
+                                })
+                            }
+                        }
+                    }
+
                     match tx.execute(
-                        "INSERT INTO vehicle_overview (id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks) 
-                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        "INSERT INTO vehicle_overview (id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks, dlc_id) 
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
                         params![
                             vehicle.id,
                             vehicle.brand_id,
@@ -264,10 +282,21 @@ pub fn add_vehicle_overview(
                             vehicle.vehicle_type,
                             vehicle.feature,
                             vehicle.price,
-                            vehicle.remarks
+                            vehicle.remarks,
+                            vehicle.dlc_id
                         ],
                     ) {
                         Ok(_) => {
+                            // 记录新载具的初始价格和所属DLC
+                            if let Err(e) = record_price_changes(&tx, true).and_then(|_| sync_dlc_releases(&tx)) {
+                                let _ = tx.rollback();
+                                return Ok(ApiResponse {
+                                    success: false,
+                                    data: None,
+                                    error: Some(e.to_string()),
+                                });
+                            }
+
                             match tx.commit() {
                                 Ok(_) => {
                                     Ok(ApiResponse {
@@ -315,7 +344,7 @@ pub fn add_vehicle_overview(
 #[command]
 pub fn update_vehicle_overview(
     app: AppHandle,
-    vehicle: VehicleOverview,
+    mut vehicle: VehicleOverview,
 ) -> Result<ApiResponse<VehicleOverview>, String> {
     let app_dir = app
         .path()
@@ -325,6 +354,14 @@ pub fn update_vehicle_overview(
 
     match Connection::open(&db_path) {
         Ok(mut conn) => {
+            // 更换品牌时需要同时重编号车库中的载具引用
+            if let Err(e) = attach_user_db(&conn, &app_dir) {
+                return Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e.to_string()),
+                });
+            }
             match conn.transaction() {
                 Ok(tx) => {
                     // 检查品牌是否存在
@@ -351,8 +388,23 @@ pub fn update_vehicle_overview(
                         });
                     }
 
+                    // 更新前读取原品牌，用于判断重编号时是否需要同步车位中的品牌名称
+                    let old_brand_id: Option<i32> = match tx
+                        .query_row("SELECT brand_id FROM vehicle_overview WHERE id = ?1", params![vehicle.id], |row| row.get(0))
+                        .optional()
+                    {
+                        Ok(brand_id) => brand_id.flatten(),
+                        Err(e) => {
+                            return Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e.to_string()),
+                            })
+                        }
+                    };
+
                     match tx.execute(
-                        "UPDATE vehicle_overview SET brand_id = ?1, vehicle_name = ?2, vehicle_name_en = ?3, vehicle_type = ?4, feature = ?5, price = ?6, remarks = ?7 WHERE id = ?8",
+                        "UPDATE vehicle_overview SET brand_id = ?1, vehicle_name = ?2, vehicle_name_en = ?3, vehicle_type = ?4, feature = ?5, price = ?6, remarks = ?7, version = version + 1 WHERE id = ?8",
                         params![
                             vehicle.brand_id,
                             vehicle.vehicle_name,
@@ -365,6 +417,54 @@ pub fn update_vehicle_overview(
                         ],
                     ) {
                         Ok(_) => {
+                            // 品牌与ID中编码的品牌不一致时，按新品牌重新分配ID
+                            let id_brand = parse_vehicle_id(&vehicle.id).map(|(brand_id, _)| brand_id);
+                            if let (Some(id_brand), Some(brand_id)) = (id_brand, vehicle.brand_id) {
+                                if id_brand != brand_id {
+                                    let rekeyed = next_vehicle_id(&tx, brand_id)
+                                        .map_err(|e| e.to_string())
+                                        .and_then(|new_id| rekey_vehicle_in(&tx, &vehicle.id, &new_id, old_brand_id));
+                                    match rekeyed {
+                                        Ok(report) => vehicle.id = report.new_id,
+                                        Err(e) => {
+                                            let _ = tx.rollback();
+                                            return Ok(ApiResponse {
+                                                success: false,
+                                                data: None,
+                                                error: Some(e),
+                                            });
+                                        }
+                                    }
+                                }
+                            }
+
+                            // 价格发生变化时记录价格历史
+                            if let Err(e) = record_price_changes(&tx, true) {
+                                let _ = tx.rollback();
+                                return Ok(ApiResponse {
+                                    success: false,
+                                    data: None,
+                                    error: Some(e.to_string()),
+                                });
+                            }
+
+                            // 返回递增后的版本号（重编号时版本号会再次递增），客户端可直接用于后续的部分更新
+                            match tx.query_row(
+                                "SELECT version FROM vehicle_overview WHERE id = ?1",
+                                params![vehicle.id],
+                                |row| row.get(0),
+                            ) {
+                                Ok(version) => vehicle.version = version,
+                                Err(e) => {
+                                    let _ = tx.rollback();
+                                    return Ok(ApiResponse {
+                                        success: false,
+                                        data: None,
+                                        error: Some(e.to_string()),
+                                    });
+                                }
+                            }
+
                             match tx.commit() {
                                 Ok(_) => {
                                     Ok(ApiResponse {
@@ -417,17 +517,30 @@ pub fn delete_vehicle_overview(app: AppHandle, id: String) -> Result<ApiResponse
         .expect("Failed to get app data directory");
     let db_path = app_dir.join("gtavm_common.db"); // 使用通用数据库文件存储载具数据
 
-    match Connection::open(&db_path) {
+    // 附加用户数据库，愿望清单中的条目随载具一起删除
+    match Connection::open(&db_path).and_then(|conn| attach_user_db(&conn, &app_dir).map(|_| conn)) {
         Ok(mut conn) => {
             match conn.transaction() {
                 Ok(tx) => {
-                    match tx.execute("DELETE FROM vehicle_overview WHERE id = ?1", params![id]) {
-                        Ok(_) => match tx.commit() {
-                            Ok(_) => Ok(ApiResponse {
-                                success: true,
-                                data: None,
-                                error: None,
-                            }),
+                    // 图片文件在事务提交后删除
+                    let deleted = vehicle_image_file(&tx, &id).and_then(|image_file| {
+                        delete_vehicle_side_data(&tx, &id)?;
+                        delete_vehicle_wishlist(&tx, &id)?;
+                        tx.execute("DELETE FROM vehicle_overview WHERE id = ?1", params![id])?;
+                        Ok(image_file)
+                    });
+                    match deleted {
+                        Ok(image_file) => match tx.commit() {
+                            Ok(_) => {
+                                if let Some(image_file) = image_file {
+                                    remove_image_files(&app_dir, &image_file);
+                                }
+                                Ok(ApiResponse {
+                                    success: true,
+                                    data: None,
+                                    error: None,
+                                })
+                            }
                             Err(e) => Ok(ApiResponse {
                                 success: false,
                                 data: None,
@@ -660,6 +773,511 @@ pub fn get_vehicle_type_dict_by_key(
     }
 }
 
+// 获取品牌下一个可用的载具ID
+#[command]
+pub fn get_next_vehicle_id(app: AppHandle, brand_id: i32) -> Result<ApiResponse<String>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    match Connection::open(&db_path) {
+        Ok(conn) => match next_vehicle_id(&conn, brand_id) {
+            Ok(id) => Ok(ApiResponse {
+                success: true,
+                data: Some(id),
+                error: None,
+            }),
+            Err(e) => Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            }),
+        },
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 修改载具ID，同时更新车库车位、心愿单以及所有记录载具ID的历史表
+#[command]
+pub fn rekey_vehicle(app: AppHandle, old_id: String, new_id: String) -> Result<ApiResponse<RekeyReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match run_rekey_vehicle(&app_dir, &old_id, &new_id) {
+        Ok(report) => Ok(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+fn run_rekey_vehicle(app_dir: &Path, old_id: &str, new_id: &str) -> Result<RekeyReport, String> {
+    let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    attach_user_db(&conn, app_dir).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let old_brand_id: Option<i32> = tx
+        .query_row("SELECT brand_id FROM vehicle_overview WHERE id = ?1", params![old_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("载具不存在: {}", old_id))?;
+    let report = rekey_vehicle_in(&tx, old_id, new_id, old_brand_id)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+// 目录中按载具ID保存数据的侧表
+const VEHICLE_SIDE_TABLES: [&str; 7] = [
+    "vehicle_dlc",
+    "catalog_vehicle",
+    "vehicle_stats",
+    "vehicle_acquisition",
+    "vehicle_price",
+    "vehicle_price_history",
+    "vehicle_image",
+];
+
+// 删除载具在目录侧表中的数据，载具ID会被新载具复用，残留数据会被误认为属于新载具
+// 图片文件不在事务中，调用方需在提交后通过image_api::remove_image_files删除
+pub(crate) fn delete_vehicle_side_data(conn: &Connection, vehicle_id: &str) -> Result<()> {
+    for table in VEHICLE_SIDE_TABLES {
+        if has_column(conn, table, "vehicle_id") {
+            conn.execute(&format!("DELETE FROM {} WHERE vehicle_id = ?1", table), params![vehicle_id])?;
+        }
+    }
+    // 改装关系中载具可能是基础载具或改装后的载具
+    if has_column(conn, "vehicle_variant", "base_id") {
+        conn.execute(
+            "DELETE FROM vehicle_variant WHERE base_id = ?1 OR variant_id = ?1",
+            params![vehicle_id],
+        )?;
+    }
+    Ok(())
+}
+
+// 删除用户库中该载具的愿望清单条目（所有角色），调用方需先附加用户数据库（user_db）
+pub(crate) fn delete_vehicle_wishlist(conn: &Connection, vehicle_id: &str) -> Result<()> {
+    if has_schema_column(conn, "user_db", "wishlist", "vehicle_id") {
+        conn.execute("DELETE FROM user_db.wishlist WHERE vehicle_id = ?1", params![vehicle_id])?;
+    }
+    Ok(())
+}
+
+// 按"品牌ID-序号"规则分配品牌下一个载具ID
+pub(crate) fn next_vehicle_id(conn: &Connection, brand_id: i32) -> Result<String> {
+    let mut stmt = conn.prepare("SELECT id FROM vehicle_overview WHERE id LIKE ?1")?;
+    let id_iter = stmt.query_map(params![format!("{}-%", brand_id)], |row| row.get::<_, String>(0))?;
+    let mut max_index = 0;
+    for id in id_iter {
+        if let Some((id_brand, index)) = parse_vehicle_id(&id?) {
+            if id_brand == brand_id {
+                max_index = max_index.max(index);
+            }
+        }
+    }
+    Ok(format!("{}-{}", brand_id, max_index + 1))
+}
+
+// 在已附加用户数据库（user_db）的事务中修改载具ID
+// old_brand_id为修改前的品牌（调用方可能已在同一事务中更新了品牌），新ID中的品牌与其不同时，
+// 同步修改载具品牌和车位中的品牌名称
+pub(crate) fn rekey_vehicle_in(
+    conn: &Connection,
+    old_id: &str,
+    new_id: &str,
+    old_brand_id: Option<i32>,
+) -> Result<RekeyReport, String> {
+    let (new_brand_id, _) = parse_vehicle_id(new_id).ok_or_else(|| format!("载具ID格式无效: {}", new_id))?;
+    if old_id == new_id {
+        return Err("新ID与原ID相同".to_string());
+    }
+
+    conn.query_row("SELECT 1 FROM vehicle_overview WHERE id = ?1", params![old_id], |_| Ok(()))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("载具不存在: {}", old_id))?;
+    let new_exists: i64 = conn
+        .query_row("SELECT COUNT(*) FROM vehicle_overview WHERE id = ?1", params![new_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if new_exists > 0 {
+        return Err(format!("载具ID已存在: {}", new_id));
+    }
+    let brand_names: Option<(Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT brand_name, brand_name_en FROM vehicle_brand WHERE id = ?1",
+            params![new_brand_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let brand_names = brand_names.ok_or_else(|| format!("品牌不存在: {}", new_brand_id))?;
+    let brand_changed = old_brand_id != Some(new_brand_id);
+
+    conn.execute(
+        "UPDATE vehicle_overview SET id = ?1, brand_id = ?2, version = version + 1 WHERE id = ?3",
+        params![new_id, new_brand_id, old_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    // 目录库和用户库中所有引用载具ID的表（价格历史、DLC、心愿单、改装关系等）
+    let mut updated_tables = Vec::new();
+    for schema in ["main", "user_db"] {
+        let tables: Vec<String> = {
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT name FROM {}.sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+                    schema
+                ))
+                .map_err(|e| e.to_string())?;
+            let name_iter = stmt.query_map([], |row| row.get(0)).map_err(|e| e.to_string())?;
+            name_iter.collect::<Result<Vec<String>>>().map_err(|e| e.to_string())?
+        };
+        for table in tables {
+            let mut changed = 0;
+            for column in VEHICLE_ID_COLUMNS {
+                if !has_schema_column(conn, schema, &table, column) {
+                    continue;
+                }
+                changed += conn
+                    .execute(
+                        &format!("UPDATE {}.\"{}\" SET {} = ?1 WHERE {} = ?2", schema, table, column, column),
+                        params![new_id, old_id],
+                    )
+                    .map_err(|e| e.to_string())?;
+            }
+            if changed > 0 {
+                updated_tables.push(format!("{}.{}", schema, table));
+            }
+        }
+    }
+
+    let updated_slots = rewrite_garage_slots(conn, "user_db", "rekeyed", |slot| {
+        if slot.get("id").and_then(|id| id.as_str()) != Some(old_id) {
+            return false;
+        }
+        slot.insert("id".to_string(), json!(new_id));
+        if brand_changed {
+            slot.insert("brandName".to_string(), json!(brand_names.0));
+            slot.insert("brandNameEn".to_string(), json!(brand_names.1));
+        }
+        true
+    })
+    .map_err(|e| e.to_string())?;
+
+    Ok(RekeyReport {
+        old_id: old_id.to_string(),
+        new_id: new_id.to_string(),
+        updated_tables,
+        updated_slots,
+    })
+}
+
+// 校验载具字段时使用的目录数据
+pub(crate) struct CatalogRefs {
+    brand_ids: HashSet<i32>,
+    vehicle_types: HashSet<String>,
+    features: HashSet<String>,
+}
+
+// 读取品牌、载具类型和特性字典
+pub(crate) fn load_catalog_refs(conn: &Connection) -> Result<CatalogRefs> {
+    let mut stmt = conn.prepare("SELECT id FROM vehicle_brand")?;
+    let brand_iter = stmt.query_map([], |row| row.get(0))?;
+    Ok(CatalogRefs {
+        brand_ids: brand_iter.collect::<Result<HashSet<i32>>>()?,
+        vehicle_types: load_dict(conn, "vehicle_type_dict")?.into_keys().collect(),
+        features: load_dict(conn, "feature_type_dict")?.into_keys().collect(),
+    })
+}
+
+// 校验品牌存在、载具类型和特性在字典中（字典为空时不校验）
+pub(crate) fn validate_vehicle_fields(
+    refs: &CatalogRefs,
+    brand_id: Option<i32>,
+    vehicle_type: Option<&str>,
+    feature: Option<&str>,
+) -> Result<(), String> {
+    if let Some(brand_id) = brand_id {
+        if !refs.brand_ids.contains(&brand_id) {
+            return Err(format!("指定的品牌不存在: {}", brand_id));
+        }
+    }
+    if let Some(vehicle_type) = vehicle_type.filter(|t| !t.is_empty()) {
+        if !refs.vehicle_types.is_empty() && !refs.vehicle_types.contains(vehicle_type) {
+            return Err(format!("未知的载具类型: {}", vehicle_type));
+        }
+    }
+    if !refs.features.is_empty() {
+        if let Some(key) = split_features(feature).into_iter().find(|key| !refs.features.contains(key)) {
+            return Err(format!("未知的特性: {}", key));
+        }
+    }
+    Ok(())
+}
+
+// 新增一辆载具，未指定ID时按品牌分配，返回实际使用的ID
+pub(crate) fn insert_vehicle(conn: &Connection, refs: &CatalogRefs, vehicle: &VehicleOverview) -> Result<String, String> {
+    let brand_id = vehicle.brand_id.ok_or_else(|| "未指定载具品牌".to_string())?;
+    validate_vehicle_fields(refs, Some(brand_id), vehicle.vehicle_type.as_deref(), vehicle.feature.as_deref())?;
+
+    let id = if vehicle.id.trim().is_empty() {
+        next_vehicle_id(conn, brand_id).map_err(|e| e.to_string())?
+    } else {
+        vehicle.id.clone()
+    };
+    conn.execute(
+        "INSERT INTO vehicle_overview (id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks, dlc_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            id,
+            brand_id,
+            vehicle.vehicle_name,
+            vehicle.vehicle_name_en,
+            vehicle.vehicle_type,
+            vehicle.feature,
+            vehicle.price,
+            vehicle.remarks,
+            vehicle.dlc_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+// 只更新补丁中提供的字段，返回更新后的载具ID
+// 补丁必须带版本号，只有ignore_version为true（批量更新显式放弃检查）时才跳过版本校验
+// 更换品牌时按新品牌重新分配ID，调用方需先附加用户数据库（user_db）
+pub(crate) fn apply_vehicle_patch(
+    conn: &Connection,
+    refs: &CatalogRefs,
+    patch: &VehiclePatch,
+    ignore_version: bool,
+) -> Result<String, String> {
+    let current = load_vehicle_by_id(conn, &patch.id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("载具不存在: {}", patch.id))?;
+    if !ignore_version {
+        let version = patch.version.ok_or_else(|| format!("缺少版本号: {}", patch.id))?;
+        check_version(&patch.id, current.version, version)?;
+    }
+    validate_vehicle_fields(refs, patch.brand_id, patch.vehicle_type.as_deref(), patch.feature.as_deref())?;
+
+    let mut columns: Vec<&str> = Vec::new();
+    let mut values: Vec<SqlValue> = Vec::new();
+    if let Some(brand_id) = patch.brand_id {
+        columns.push("brand_id");
+        values.push(SqlValue::Integer(brand_id.into()));
+    }
+    for (column, value) in [
+        ("vehicle_name", &patch.vehicle_name),
+        ("vehicle_name_en", &patch.vehicle_name_en),
+        ("vehicle_type", &patch.vehicle_type),
+        ("feature", &patch.feature),
+        ("remarks", &patch.remarks),
+    ] {
+        if let Some(value) = value {
+            columns.push(column);
+            values.push(SqlValue::Text(value.clone()));
+        }
+    }
+    for (column, value) in [("price", patch.price), ("dlc_id", patch.dlc_id)] {
+        if let Some(value) = value {
+            columns.push(column);
+            values.push(SqlValue::Integer(value.into()));
+        }
+    }
+    if columns.is_empty() {
+        return Ok(patch.id.clone());
+    }
+
+    let set_clause: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| format!("{} = ?{}", column, index + 1))
+        .collect();
+    values.push(SqlValue::Text(patch.id.clone()));
+    conn.execute(
+        &format!(
+            "UPDATE vehicle_overview SET {}, version = version + 1 WHERE id = ?{}",
+            set_clause.join(", "),
+            values.len()
+        ),
+        params_from_iter(values),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let id_brand = parse_vehicle_id(&patch.id).map(|(brand_id, _)| brand_id);
+    match (id_brand, patch.brand_id) {
+        (Some(id_brand), Some(brand_id)) if id_brand != brand_id => {
+            let new_id = next_vehicle_id(conn, brand_id).map_err(|e| e.to_string())?;
+            rekey_vehicle_in(conn, &patch.id, &new_id, current.brand_id).map(|report| report.new_id)
+        }
+        _ => Ok(patch.id.clone()),
+    }
+}
+
+// 版本号不一致时返回冲突错误
+pub(crate) fn check_version(id: &str, current: Option<i64>, expected: i64) -> Result<(), String> {
+    let current = current.unwrap_or(0);
+    if current != expected {
+        return Err(format!(
+            "{}: {}已被修改（当前版本{}，提交版本{}），请刷新后重试",
+            VERSION_CONFLICT, id, current, expected
+        ));
+    }
+    Ok(())
+}
+
+// 按ID读取载具
+pub(crate) fn load_vehicle_by_id(conn: &Connection, id: &str) -> Result<Option<VehicleOverview>> {
+    conn.query_row(
+        "SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks, dlc_id, version FROM vehicle_overview WHERE id = ?1",
+        params![id],
+        map_vehicle_overview,
+    )
+    .optional()
+}
+
+// 部分更新载具，只修改提供的字段
+// 版本号与当前不一致时拒绝写入，并在data中返回当前数据以便客户端合并
+#[command]
+pub fn patch_vehicle_overview(app: AppHandle, patch: VehiclePatch) -> Result<ApiResponse<VehicleOverview>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    if patch.version.is_none() {
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("缺少版本号".to_string()),
+        });
+    }
+
+    match run_patch_vehicle(&app_dir, &patch) {
+        Ok(PatchOutcome::Updated(vehicle)) => Ok(ApiResponse {
+            success: true,
+            data: Some(vehicle),
+            error: None,
+        }),
+        Ok(PatchOutcome::Conflict(e, current)) => Ok(ApiResponse {
+            success: false,
+            data: Some(current),
+            error: Some(e),
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 部分更新的结果，版本冲突时附带当前数据
+pub(crate) enum PatchOutcome<T> {
+    Updated(T),
+    Conflict(String, T),
+}
+
+// 执行载具部分更新
+fn run_patch_vehicle(app_dir: &Path, patch: &VehiclePatch) -> Result<PatchOutcome<VehicleOverview>, String> {
+    let mut conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    // 更换品牌时需要同时重编号车库中的载具引用
+    attach_user_db(&conn, app_dir).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let refs = load_catalog_refs(&tx).map_err(|e| e.to_string())?;
+    match apply_vehicle_patch(&tx, &refs, patch, false) {
+        Ok(id) => {
+            record_price_changes(&tx, true).map_err(|e| e.to_string())?;
+            let updated = load_vehicle_by_id(&tx, &id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("载具不存在: {}", id))?;
+            tx.commit().map_err(|e| e.to_string())?;
+            Ok(PatchOutcome::Updated(updated))
+        }
+        Err(e) if e.starts_with(VERSION_CONFLICT) => match load_vehicle_by_id(&tx, &patch.id) {
+            Ok(Some(current)) => Ok(PatchOutcome::Conflict(e, current)),
+            _ => Err(e),
+        },
+        Err(e) => Err(e),
+    }
+}
+
+// 检查指定库中的表是否包含某字段
+fn has_schema_column(conn: &Connection, schema: &str, table: &str, column: &str) -> bool {
+    let mut stmt = match conn.prepare(&format!("PRAGMA {}.table_info(\"{}\")", schema, table)) {
+        Ok(stmt) => stmt,
+        Err(_) => return false,
+    };
+    let names: Vec<String> = match stmt.query_map([], |row| row.get::<usize, String>(1)) {
+        Ok(rows) => rows.flatten().collect(),
+        Err(_) => return false,
+    };
+    names.iter().any(|name| name == column)
+}
+
+// 读取价格字段，兼容以文本形式存储的价格
+pub(crate) fn read_price(row: &rusqlite::Row, index: usize) -> Option<i32> {
+    match row.get(index) {
+        Ok(price_val) => price_val,
+        Err(_) => match row.get::<_, String>(index) {
+            Ok(text_price) => text_price.parse::<i32>().ok(),
+            Err(_) => None,
+        },
+    }
+}
+
+// 拆分以逗号分隔的特性字段
+pub(crate) fn split_features(feature: Option<&str>) -> Vec<String> {
+    feature
+        .unwrap_or("")
+        .split(',')
+        .map(|key| key.trim())
+        .filter(|key| !key.is_empty())
+        .map(|key| key.to_string())
+        .collect()
+}
+
+// 读取所有载具概览
+pub(crate) fn load_vehicle_overviews(conn: &Connection) -> Result<Vec<VehicleOverview>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, brand_id, vehicle_name, vehicle_name_en, vehicle_type, feature, price, remarks, dlc_id, version FROM vehicle_overview",
+    )?;
+    let vehicle_iter = stmt.query_map([], map_vehicle_overview)?;
+    vehicle_iter.collect()
+}
+
+// 将查询结果映射为载具概览，字段顺序与load_vehicle_overviews一致
+pub(crate) fn map_vehicle_overview(row: &rusqlite::Row) -> Result<VehicleOverview> {
+    Ok(VehicleOverview {
+        id: row.get(0)?,
+        brand_id: row.get(1)?,
+        vehicle_name: row.get(2)?,
+        vehicle_name_en: row.get(3)?,
+        vehicle_type: row.get(4)?,
+        feature: row.get(5)?,
+        price: read_price(row, 6),
+        remarks: row.get(7)?,
+        dlc_id: row.get(8)?,
+        version: row.get(9)?,
+        acquisition: None,
+    })
+}
+
 // 更新前端调用的greet函数
 #[command]
 pub fn greet(name: &str) -> Result<String, String> {
@@ -668,3 +1286,130 @@ pub fn greet(name: &str) -> Result<String, String> {
         name
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::catalog_db;
+
+    fn setup() -> Connection {
+        let conn = catalog_db();
+        conn.execute_batch(
+            "INSERT INTO vehicle_brand (id, brand_name, brand_name_en) VALUES (1, '佩嘉西', 'Pegassi'), (2, '格罗蒂', 'Grotti');
+             INSERT INTO vehicle_overview (id, brand_id) VALUES ('1-1', 1), ('1-3', 1), ('2-1', 2);
+             INSERT INTO vehicle_dlc (vehicle_id, dlc_id) VALUES ('1-3', 5);
+             INSERT INTO vehicle_price (vehicle_id, price_kind, price) VALUES ('1-3', 'buy', 100);
+             INSERT INTO user_db.wishlist (vehicle_id) VALUES ('1-3'), ('1-1');",
+        )
+        .unwrap();
+        let vehicle_list = json!([{"id": "1-3", "brandName": "佩嘉西", "brandNameEn": "Pegassi"}, {"id": "1-1"}, null]);
+        conn.execute(
+            "INSERT INTO user_db.garage_overview (garage_name, num, vehicle_list, profile_id) VALUES ('车库', 3, ?1, 1)",
+            params![vehicle_list.to_string()],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn garage_slots(conn: &Connection) -> serde_json::Value {
+        let list: String = conn
+            .query_row("SELECT vehicle_list FROM user_db.garage_overview", [], |row| row.get(0))
+            .unwrap();
+        serde_json::from_str(&list).unwrap()
+    }
+
+    #[test]
+    fn next_vehicle_id_follows_highest_index_of_brand() {
+        let conn = setup();
+        assert_eq!(next_vehicle_id(&conn, 1).unwrap(), "1-4");
+        assert_eq!(next_vehicle_id(&conn, 2).unwrap(), "2-2");
+        assert_eq!(next_vehicle_id(&conn, 3).unwrap(), "3-1");
+    }
+
+    #[test]
+    fn rekey_updates_catalog_and_user_data() {
+        let conn = setup();
+        let report = rekey_vehicle_in(&conn, "1-3", "2-2", Some(1)).unwrap();
+        assert_eq!(report.updated_slots.len(), 1);
+        assert!(report.updated_tables.contains(&"main.vehicle_dlc".to_string()));
+        assert!(report.updated_tables.contains(&"user_db.wishlist".to_string()));
+
+        let brand_id: i32 = conn
+            .query_row("SELECT brand_id FROM vehicle_overview WHERE id = '2-2'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(brand_id, 2);
+        let price: i64 = conn
+            .query_row("SELECT price FROM vehicle_price WHERE vehicle_id = '2-2'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(price, 100);
+        let wishlist: Vec<String> = conn
+            .prepare("SELECT vehicle_id FROM user_db.wishlist ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(wishlist, vec!["2-2", "1-1"]);
+
+        let slots = garage_slots(&conn);
+        assert_eq!(slots[0]["id"], "2-2");
+        assert_eq!(slots[0]["brandNameEn"], "Grotti");
+        assert_eq!(slots[1]["id"], "1-1");
+    }
+
+    #[test]
+    fn rekey_rejects_existing_or_unknown_ids() {
+        let conn = setup();
+        assert!(rekey_vehicle_in(&conn, "1-3", "1-1", Some(1)).is_err());
+        assert!(rekey_vehicle_in(&conn, "1-9", "1-10", Some(1)).is_err());
+        assert!(rekey_vehicle_in(&conn, "1-3", "9-1", Some(1)).is_err());
+        assert_eq!(garage_slots(&conn)[0]["id"], "1-3");
+    }
+
+    fn price_patch(version: Option<i64>) -> VehiclePatch {
+        VehiclePatch {
+            id: "1-1".to_string(),
+            price: Some(500),
+            version,
+            ..Default::default()
+        }
+    }
+
+    fn vehicle_version(conn: &Connection, id: &str) -> i64 {
+        conn.query_row("SELECT version FROM vehicle_overview WHERE id = ?1", params![id], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn check_version_reports_conflict() {
+        assert!(check_version("1-1", Some(2), 2).is_ok());
+        assert!(check_version("1-1", None, 0).is_ok());
+        let err = check_version("1-1", Some(3), 2).unwrap_err();
+        assert!(err.starts_with(VERSION_CONFLICT));
+    }
+
+    #[test]
+    fn patch_with_stale_version_is_rejected() {
+        let conn = setup();
+        let refs = load_catalog_refs(&conn).unwrap();
+        let version = vehicle_version(&conn, "1-1");
+        assert_eq!(apply_vehicle_patch(&conn, &refs, &price_patch(Some(version)), false).unwrap(), "1-1");
+        assert_eq!(vehicle_version(&conn, "1-1"), version + 1);
+
+        let err = apply_vehicle_patch(&conn, &refs, &price_patch(Some(version)), false).unwrap_err();
+        assert!(err.starts_with(VERSION_CONFLICT));
+        assert_eq!(vehicle_version(&conn, "1-1"), version + 1);
+    }
+
+    #[test]
+    fn patch_without_version_requires_explicit_opt_out() {
+        let conn = setup();
+        let refs = load_catalog_refs(&conn).unwrap();
+        let version = vehicle_version(&conn, "1-1");
+        assert!(apply_vehicle_patch(&conn, &refs, &price_patch(None), false).is_err());
+        assert_eq!(vehicle_version(&conn, "1-1"), version);
+
+        apply_vehicle_patch(&conn, &refs, &price_patch(None), true).unwrap();
+        assert_eq!(vehicle_version(&conn, "1-1"), version + 1);
+    }
+}
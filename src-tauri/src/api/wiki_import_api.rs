@@ -0,0 +1,403 @@
+// 导入必要的依赖
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::brand_api::load_vehicle_brands;
+use crate::api::integrity_api::parse_vehicle_id;
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::models::{ApiResponse, WikiImportReport, WikiMatch, WikiNewVehicle};
+
+// Wiki表格中的一行载具数据
+struct WikiRow {
+    vehicle: String,
+    style: Option<String>,
+    influence: Option<String>,
+    notes: Option<String>,
+}
+
+// 名称归一化：只保留字母和数字并转为小写，用于英文名称匹配
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+// 去除HTML标签并解码常见实体，<br>视为分隔符
+fn html_to_text(fragment: &str) -> String {
+    let mut text = String::new();
+    let mut tag = String::new();
+    let mut in_tag = false;
+    for c in fragment.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                if tag.trim_start().to_ascii_lowercase().starts_with("br") {
+                    text.push_str(" / ");
+                }
+            }
+            _ if in_tag => tag.push(c),
+            _ => text.push(c),
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    text.trim_matches(|c: char| c == '/' || c.is_whitespace()).to_string()
+}
+
+// 提取start_tag与end_tag之间的所有元素，返回开始标签的属性部分和元素内容
+fn extract_elements<'a>(html: &'a str, lower: &str, start_tag: &str, end_tag: &str) -> Vec<(&'a str, &'a str)> {
+    let mut elements = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find(start_tag) {
+        let start = pos + start;
+        let content_start = match lower[start..].find('>') {
+            Some(offset) => start + offset + 1,
+            None => break,
+        };
+        let content_end = match lower[content_start..].find(end_tag) {
+            Some(offset) => content_start + offset,
+            None => lower.len(),
+        };
+        elements.push((&html[start + start_tag.len()..content_start - 1], &html[content_start..content_end]));
+        pos = content_end;
+    }
+    elements
+}
+
+// 读取单元格的rowspan属性
+fn row_span(attrs: &str) -> usize {
+    let lower = attrs.to_ascii_lowercase();
+    lower
+        .find("rowspan=")
+        .map(|start| {
+            lower[start + "rowspan=".len()..]
+                .trim_start_matches(['"', '\''])
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>()
+        })
+        .and_then(|span| span.parse().ok())
+        .unwrap_or(1)
+        .max(1)
+}
+
+// 解析Wiki品牌表格，需要包含Vehicle（或Name）列，Style、Influence和Notes列可选
+fn parse_wiki_table(html: &str) -> Vec<WikiRow> {
+    // ASCII小写转换不改变字节位置，可以用于不区分大小写的查找
+    let lower = html.to_ascii_lowercase();
+    let headers: Vec<String> = extract_elements(html, &lower, "<th", "</th>")
+        .into_iter()
+        .map(|(_, header)| html_to_text(header).to_lowercase())
+        .collect();
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let vehicle_column = match column("vehicle").or_else(|| column("name")) {
+        Some(index) => index,
+        None => return Vec::new(),
+    };
+    let style_column = column("style");
+    let influence_column = column("influence");
+    let notes_column = column("notes");
+
+    // 跨行单元格在后续行中缺失，记录剩余行数和内容用于补齐
+    let mut spans: Vec<(usize, String)> = vec![(0, String::new()); headers.len()];
+    let mut rows: Vec<WikiRow> = Vec::new();
+    for (_, row) in extract_elements(html, &lower, "<tr", "</tr>") {
+        let row_lower = row.to_ascii_lowercase();
+        let mut cells_iter = extract_elements(row, &row_lower, "<td", "</td>").into_iter();
+        let mut cells = Vec::with_capacity(headers.len());
+        let mut vehicle_spanned = false;
+        for (index, span) in spans.iter_mut().enumerate() {
+            if span.0 > 0 {
+                span.0 -= 1;
+                cells.push(span.1.clone());
+                vehicle_spanned |= index == vehicle_column;
+            } else if let Some((attrs, content)) = cells_iter.next() {
+                let text = html_to_text(content);
+                *span = (row_span(attrs) - 1, text.clone());
+                cells.push(text);
+            }
+        }
+
+        // 载具名称跨行时，该行只是上一辆载具的补充原型
+        if vehicle_spanned {
+            let extra = influence_column
+                .and_then(|index| cells.get(index))
+                .filter(|value| !value.is_empty());
+            if let (Some(previous), Some(extra)) = (rows.last_mut(), extra) {
+                previous.influence = Some(match previous.influence.take() {
+                    Some(influence) => format!("{} / {}", influence, extra),
+                    None => extra.clone(),
+                });
+            }
+            continue;
+        }
+
+        let vehicle = match cells.get(vehicle_column) {
+            Some(vehicle) if !vehicle.is_empty() => vehicle.clone(),
+            _ => continue,
+        };
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| cells.get(index))
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+        rows.push(WikiRow {
+            vehicle,
+            style: cell(style_column),
+            influence: cell(influence_column),
+            notes: cell(notes_column),
+        });
+    }
+    rows
+}
+
+// SQL字符串转义
+fn sql_text(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("'{}'", value.replace('\'', "''")),
+        None => "NULL".to_string(),
+    }
+}
+
+// 确定Wiki表格所在目录：未指定时使用开发目录下的public/gta
+fn resolve_wiki_dir(html_dir: Option<String>) -> Result<PathBuf, String> {
+    if let Some(dir) = html_dir {
+        return Ok(PathBuf::from(dir));
+    }
+    let current_dir = env::current_dir().map_err(|e| e.to_string())?;
+    for candidate in [current_dir.join("public").join("gta"), current_dir.join("..").join("public").join("gta")] {
+        if candidate.is_dir() {
+            return Ok(candidate);
+        }
+    }
+    Err("未找到Wiki表格目录，请指定html_dir".to_string())
+}
+
+// 解析品牌HTML表格并与现有目录比较，生成目录补丁
+fn build_wiki_import(wiki_dir: &Path, conn: &Connection) -> Result<WikiImportReport, String> {
+    let brands = load_vehicle_brands(conn).map_err(|e| e.to_string())?;
+    let vehicles = load_vehicle_overviews(conn).map_err(|e| e.to_string())?;
+
+    let brand_by_name: HashMap<String, (i32, Option<String>)> = brands
+        .iter()
+        .filter_map(|brand| {
+            brand
+                .brand_name_en
+                .as_ref()
+                .map(|name| (normalize_name(name), (brand.id, brand.brand_name_en.clone())))
+        })
+        .collect();
+    let vehicle_by_name: HashMap<String, usize> = vehicles
+        .iter()
+        .enumerate()
+        .filter_map(|(index, vehicle)| {
+            vehicle
+                .vehicle_name_en
+                .as_ref()
+                .map(|name| (normalize_name(name), index))
+        })
+        .collect();
+
+    // 按品牌-序号规则为新载具分配ID
+    let mut next_index: HashMap<i32, u32> = HashMap::new();
+    for vehicle in &vehicles {
+        if let Some((brand, index)) = parse_vehicle_id(&vehicle.id) {
+            let next = next_index.entry(brand).or_insert(1);
+            *next = (*next).max(index + 1);
+        }
+    }
+
+    // 只读取目录顶层的HTML文件，子目录中是改装店等非品牌表格
+    let mut files: Vec<PathBuf> = std::fs::read_dir(wiki_dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| ext.eq_ignore_ascii_case("html"))
+                    .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+
+    let mut report = WikiImportReport {
+        matched: Vec::new(),
+        brand_assignments: Vec::new(),
+        new_vehicles: Vec::new(),
+        unknown_brand_files: Vec::new(),
+        patch_sql: String::new(),
+    };
+    let mut proposed_names: HashSet<String> = HashSet::new();
+    let mut reassigned_ids: HashSet<String> = HashSet::new();
+
+    for path in files {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (brand_id, brand_name_en) = match brand_by_name.get(&normalize_name(&stem)) {
+            Some(brand) => brand.clone(),
+            None => {
+                report.unknown_brand_files.push(file_name);
+                continue;
+            }
+        };
+
+        let html = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        for row in parse_wiki_table(&html) {
+            let key = normalize_name(&row.vehicle);
+            if let Some(&index) = vehicle_by_name.get(&key) {
+                let vehicle = &vehicles[index];
+                let mut matched = WikiMatch {
+                    vehicle_id: vehicle.id.clone(),
+                    vehicle_name_en: vehicle.vehicle_name_en.clone(),
+                    current_brand_id: vehicle.brand_id,
+                    wiki_brand_id: brand_id,
+                    source_file: file_name.clone(),
+                    proposed_id: None,
+                };
+                if vehicle.brand_id != Some(brand_id) {
+                    // 同一载具出现在多个品牌表格中时只分配一次
+                    if !reassigned_ids.insert(vehicle.id.clone()) {
+                        continue;
+                    }
+                    // 更换品牌后ID中的品牌也需要改变，与新载具使用同一套序号分配新ID
+                    matched.proposed_id = Some(allocate_id(&mut next_index, brand_id));
+                    report.brand_assignments.push(matched);
+                } else {
+                    report.matched.push(matched);
+                }
+            } else if proposed_names.insert(key) {
+                let proposed_id = allocate_id(&mut next_index, brand_id);
+                report.new_vehicles.push(WikiNewVehicle {
+                    proposed_id,
+                    vehicle_name_en: row.vehicle,
+                    brand_id,
+                    brand_name_en: brand_name_en.clone(),
+                    style: row.style,
+                    influence: row.influence,
+                    notes: row.notes,
+                    source_file: file_name.clone(),
+                });
+            }
+        }
+    }
+
+    // 生成可审阅的SQL补丁，Wiki中的Style、Influence和Notes写入备注
+    // 更换品牌需要同时修改载具ID及车库、愿望清单等处的引用，无法用SQL表达，
+    // 以注释列出，审阅后通过rekey_vehicle执行（新ID中的品牌即为新品牌）
+    let mut patch = String::from("-- Catalog patch generated from wiki brand tables\n");
+    for assignment in &report.brand_assignments {
+        patch.push_str(&format!(
+            "-- REKEY {} -> {} (brand {} -> {}): {} ({})\n",
+            assignment.vehicle_id,
+            assignment.proposed_id.clone().unwrap_or_default(),
+            assignment.current_brand_id.map(|id| id.to_string()).unwrap_or_else(|| "NULL".to_string()),
+            assignment.wiki_brand_id,
+            assignment.vehicle_name_en.clone().unwrap_or_default(),
+            assignment.source_file
+        ));
+    }
+    for vehicle in &report.new_vehicles {
+        let remarks: Vec<String> = [
+            vehicle.style.as_ref().map(|v| format!("Style: {}", v)),
+            vehicle.influence.as_ref().map(|v| format!("Influence: {}", v)),
+            vehicle.notes.as_ref().map(|v| format!("Notes: {}", v)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let remarks = if remarks.is_empty() {
+            None
+        } else {
+            Some(remarks.join("; "))
+        };
+        patch.push_str(&format!(
+            "INSERT INTO vehicle_overview (id, brand_id, vehicle_name_en, remarks) VALUES ({}, {}, {}, {});\n",
+            sql_text(&Some(vehicle.proposed_id.clone())),
+            vehicle.brand_id,
+            sql_text(&Some(vehicle.vehicle_name_en.clone())),
+            sql_text(&remarks)
+        ));
+    }
+    report.patch_sql = patch;
+
+    Ok(report)
+}
+
+// 按品牌-序号规则分配下一个ID
+fn allocate_id(next_index: &mut HashMap<i32, u32>, brand_id: i32) -> String {
+    let next = next_index.entry(brand_id).or_insert(1);
+    let id = format!("{}-{}", brand_id, next);
+    *next += 1;
+    id
+}
+
+// 从本地Wiki品牌表格导入载具，生成待审阅的目录补丁（不直接修改数据库）
+#[command]
+pub fn import_wiki_tables(
+    app: AppHandle,
+    html_dir: Option<String>,
+    output_path: Option<String>,
+) -> Result<ApiResponse<WikiImportReport>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_common.db");
+
+    let result = resolve_wiki_dir(html_dir).and_then(|wiki_dir| {
+        let conn = Connection::open(&db_path).map_err(|e| e.to_string())?;
+        build_wiki_import(&wiki_dir, &conn)
+    });
+
+    match result {
+        Ok(report) => {
+            // 指定输出路径时将补丁写入文件
+            if let Some(output_path) = output_path {
+                if let Err(e) = std::fs::write(&output_path, &report.patch_sql) {
+                    return Ok(ApiResponse {
+                        success: false,
+                        data: None,
+                        error: Some(e.to_string()),
+                    });
+                }
+            }
+            Ok(ApiResponse {
+                success: true,
+                data: Some(report),
+                error: None,
+            })
+        }
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
@@ -0,0 +1,482 @@
+// 导入必要的依赖
+use std::collections::{HashMap, HashSet};
+
+use rusqlite::{params, Connection, Result};
+use tauri::{command, AppHandle, Manager};
+
+// 导入数据模型
+use crate::api::garage_api::{load_garages, owned_vehicle_ids};
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::database::ACTIVE_PROFILE;
+use crate::models::{ApiResponse, BudgetPlan, VehicleOverview, WishlistEntry};
+
+// 读取当前角色的愿望清单（按sort_order排序）
+fn load_wishlist(conn: &Connection) -> Result<Vec<WishlistEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, vehicle_id, priority, target_garage_id, notes, sort_order FROM wishlist WHERE profile_id = {} ORDER BY sort_order ASC, id ASC",
+        ACTIVE_PROFILE
+    ))?;
+    let entry_iter = stmt.query_map([], |row| {
+        Ok(WishlistEntry {
+            id: Some(row.get(0)?),
+            vehicle_id: row.get(1)?,
+            priority: row.get(2)?,
+            target_garage_id: row.get(3)?,
+            notes: row.get(4)?,
+            sort_order: row.get(5)?,
+            vehicle_name: None,
+            price: None,
+            owned: None,
+        })
+    })?;
+    entry_iter.collect()
+}
+
+// 读取愿望清单，并补充载具名称、价格和拥有状态
+fn load_enriched_wishlist(app_dir: &std::path::Path) -> Result<Vec<WishlistEntry>, String> {
+    let user_conn = Connection::open(app_dir.join("gtavm_user.db")).map_err(|e| e.to_string())?;
+    let mut entries = load_wishlist(&user_conn).map_err(|e| e.to_string())?;
+    let garages = load_garages(&user_conn).map_err(|e| e.to_string())?;
+    let owned = owned_vehicle_ids(&garages);
+
+    let common_conn = Connection::open(app_dir.join("gtavm_common.db")).map_err(|e| e.to_string())?;
+    let vehicles: HashMap<String, VehicleOverview> = load_vehicle_overviews(&common_conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|v| (v.id.clone(), v))
+        .collect();
+
+    for entry in &mut entries {
+        if let Some(vehicle) = vehicles.get(&entry.vehicle_id) {
+            entry.vehicle_name = vehicle.vehicle_name.clone();
+            entry.price = vehicle.price;
+        }
+        entry.owned = Some(owned.contains(&entry.vehicle_id));
+    }
+    Ok(entries)
+}
+
+// 优先级的取值范围，优先级同时是预算规划中的价值，范围限制了规划表的大小
+pub const MIN_PRIORITY: i32 = 1;
+pub const MAX_PRIORITY: i32 = 10;
+
+// 优先级作为背包问题中的价值，未设置时按1计算，超出范围的旧数据按边界值计算
+fn priority_weight(entry: &WishlistEntry) -> usize {
+    entry.priority.unwrap_or(MIN_PRIORITY).clamp(MIN_PRIORITY, MAX_PRIORITY) as usize
+}
+
+// 在预算内选出优先级总和最高的组合，优先级相同时选择花费更少的组合
+fn select_by_priority(candidates: &[WishlistEntry], budget: i64) -> HashSet<usize> {
+    let total: usize = candidates.iter().map(priority_weight).sum();
+    // best[v]：优先级总和为v时的最小花费；taken[i][v]：处理第i个条目时是否因选中它而更新了best[v]
+    let mut best: Vec<Option<i64>> = vec![None; total + 1];
+    best[0] = Some(0);
+    let mut taken = vec![vec![false; total + 1]; candidates.len()];
+
+    for (index, entry) in candidates.iter().enumerate() {
+        let weight = priority_weight(entry);
+        let cost = entry.price.unwrap_or(0) as i64;
+        for value in (weight..=total).rev() {
+            let candidate = match best[value - weight] {
+                Some(prev_cost) if prev_cost + cost <= budget => prev_cost + cost,
+                _ => continue,
+            };
+            if best[value].map(|current| candidate < current).unwrap_or(true) {
+                best[value] = Some(candidate);
+                taken[index][value] = true;
+            }
+        }
+    }
+
+    // 从可达的最高优先级总和倒推选中的条目
+    let mut value = (0..=total).rev().find(|value| best[*value].is_some()).unwrap_or(0);
+    let mut chosen = HashSet::new();
+    for index in (0..candidates.len()).rev() {
+        if taken[index][value] {
+            chosen.insert(index);
+            value -= priority_weight(&candidates[index]);
+        }
+    }
+    chosen
+}
+
+// 获取愿望清单
+#[command]
+pub fn get_wishlist(app: AppHandle) -> Result<ApiResponse<Vec<WishlistEntry>>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    match load_enriched_wishlist(&app_dir) {
+        Ok(entries) => Ok(ApiResponse {
+            success: true,
+            data: Some(entries),
+            error: None,
+        }),
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e),
+        }),
+    }
+}
+
+// 添加愿望清单条目
+#[command]
+pub fn add_wishlist_entry(
+    app: AppHandle,
+    entry: WishlistEntry,
+) -> Result<ApiResponse<WishlistEntry>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    if let Some(priority) = entry.priority.filter(|p| !(MIN_PRIORITY..=MAX_PRIORITY).contains(p)) {
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("优先级{}无效，应在{}到{}之间", priority, MIN_PRIORITY, MAX_PRIORITY)),
+        });
+    }
+
+    // 检查载具是否存在于载具目录中
+    let vehicle_exists: i64 = match Connection::open(app_dir.join("gtavm_common.db")).and_then(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM vehicle_overview WHERE id = ?1",
+            params![entry.vehicle_id],
+            |row| row.get(0),
+        )
+    }) {
+        Ok(count) => count,
+        Err(e) => {
+            return Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            })
+        }
+    };
+
+    if vehicle_exists == 0 {
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("指定的载具不存在".to_string()),
+        });
+    }
+
+    match Connection::open(app_dir.join("gtavm_user.db")) {
+        Ok(mut conn) => {
+            match conn.transaction() {
+                Ok(tx) => {
+                    // 检查目标车库是否存在
+                    if let Some(garage_id) = entry.target_garage_id {
+                        let garage_exists: i64 = tx
+                            .query_row(
+                                &format!("SELECT COUNT(*) FROM garage_overview WHERE id = ?1 AND profile_id = {}", ACTIVE_PROFILE),
+                                params![garage_id],
+                                |row| row.get(0),
+                            )
+                            .unwrap_or(0);
+                        if garage_exists == 0 {
+                            return Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some("目标车库不存在".to_string()),
+                            });
+                        }
+                    }
+
+                    // 新条目默认排在最后
+                    let max_order: Option<i32> = tx
+                        .query_row(
+                            &format!("SELECT MAX(sort_order) FROM wishlist WHERE profile_id = {}", ACTIVE_PROFILE),
+                            [],
+                            |row| row.get(0),
+                        )
+                        .unwrap_or(None);
+                    let sort_order = entry.sort_order.unwrap_or(max_order.map(|o| o + 1).unwrap_or(1));
+
+                    match tx.execute(
+                        &format!(
+                            "INSERT INTO wishlist (vehicle_id, priority, target_garage_id, notes, sort_order, profile_id)
+                             VALUES (?1, ?2, ?3, ?4, ?5, {})",
+                            ACTIVE_PROFILE
+                        ),
+                        params![
+                            entry.vehicle_id,
+                            entry.priority,
+                            entry.target_garage_id,
+                            entry.notes,
+                            sort_order
+                        ],
+                    ) {
+                        Ok(_) => {
+                            let last_id = tx.last_insert_rowid() as i32;
+                            match tx.commit() {
+                                Ok(_) => Ok(ApiResponse {
+                                    success: true,
+                                    data: Some(WishlistEntry {
+                                        id: Some(last_id),
+                                        sort_order: Some(sort_order),
+                                        ..entry
+                                    }),
+                                    error: None,
+                                }),
+                                Err(e) => Ok(ApiResponse {
+                                    success: false,
+                                    data: None,
+                                    error: Some(e.to_string()),
+                                }),
+                            }
+                        }
+                        Err(e) => {
+                            // 回滚事务
+                            let _ = tx.rollback();
+                            Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e.to_string()),
+                            })
+                        }
+                    }
+                }
+                Err(e) => Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 删除愿望清单条目
+#[command]
+pub fn delete_wishlist_entry(app: AppHandle, id: i32) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_user.db");
+
+    match Connection::open(&db_path) {
+        Ok(conn) => match conn.execute("DELETE FROM wishlist WHERE id = ?1", params![id]) {
+            Ok(_) => Ok(ApiResponse {
+                success: true,
+                data: None,
+                error: None,
+            }),
+            Err(e) => Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            }),
+        },
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 按给定的ID顺序重新排列愿望清单
+#[command]
+pub fn reorder_wishlist(app: AppHandle, ids: Vec<i32>) -> Result<ApiResponse<()>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+    let db_path = app_dir.join("gtavm_user.db");
+
+    match Connection::open(&db_path) {
+        Ok(mut conn) => {
+            match conn.transaction() {
+                Ok(tx) => {
+                    for (index, id) in ids.iter().enumerate() {
+                        if let Err(e) = tx.execute(
+                            "UPDATE wishlist SET sort_order = ?1 WHERE id = ?2",
+                            params![index as i32 + 1, id],
+                        ) {
+                            // 回滚事务
+                            let _ = tx.rollback();
+                            return Ok(ApiResponse {
+                                success: false,
+                                data: None,
+                                error: Some(e.to_string()),
+                            });
+                        }
+                    }
+                    match tx.commit() {
+                        Ok(_) => Ok(ApiResponse {
+                            success: true,
+                            data: None,
+                            error: None,
+                        }),
+                        Err(e) => Ok(ApiResponse {
+                            success: false,
+                            data: None,
+                            error: Some(e.to_string()),
+                        }),
+                    }
+                }
+                Err(e) => Ok(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+        Err(e) => Ok(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+// 根据可用资金规划愿望清单的购买方案
+#[command]
+pub fn plan_budget(app: AppHandle, available_money: i64) -> Result<ApiResponse<BudgetPlan>, String> {
+    let app_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data directory");
+
+    let entries = match load_enriched_wishlist(&app_dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return Ok(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(e),
+            })
+        }
+    };
+
+    // 已拥有和没有价格的载具不参与规划
+    let mut candidates = Vec::new();
+    let mut already_owned = Vec::new();
+    let mut unpriced = Vec::new();
+    for entry in entries {
+        if entry.owned == Some(true) {
+            already_owned.push(entry);
+        } else if entry.price.is_none() {
+            unpriced.push(entry);
+        } else {
+            candidates.push(entry);
+        }
+    }
+
+    let chosen = select_by_priority(&candidates, available_money);
+    let mut selected = Vec::new();
+    let mut not_selected = Vec::new();
+    for (index, entry) in candidates.into_iter().enumerate() {
+        if chosen.contains(&index) {
+            selected.push(entry);
+        } else {
+            not_selected.push(entry);
+        }
+    }
+    let total_cost: i64 = selected.iter().map(|e| e.price.unwrap_or(0) as i64).sum();
+
+    Ok(ApiResponse {
+        success: true,
+        data: Some(BudgetPlan {
+            available_money,
+            total_cost,
+            remaining: available_money - total_cost,
+            selected,
+            not_selected,
+            already_owned,
+            unpriced,
+        }),
+        error: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(priority: Option<i32>, price: i32) -> WishlistEntry {
+        WishlistEntry {
+            id: None,
+            vehicle_id: String::new(),
+            priority,
+            target_garage_id: None,
+            notes: None,
+            sort_order: None,
+            vehicle_name: None,
+            price: Some(price),
+            owned: None,
+        }
+    }
+
+    fn selected(candidates: &[WishlistEntry], budget: i64) -> Vec<usize> {
+        let mut chosen: Vec<usize> = select_by_priority(candidates, budget).into_iter().collect();
+        chosen.sort();
+        chosen
+    }
+
+    #[test]
+    fn empty_candidates_select_nothing() {
+        assert!(select_by_priority(&[], 1_000_000).is_empty());
+    }
+
+    #[test]
+    fn selects_everything_within_budget() {
+        let candidates = [entry(Some(3), 100), entry(Some(5), 200), entry(None, 50)];
+        assert_eq!(selected(&candidates, 350), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn nothing_affordable_selects_nothing() {
+        let candidates = [entry(Some(10), 500), entry(Some(1), 200)];
+        assert!(select_by_priority(&candidates, 100).is_empty());
+    }
+
+    #[test]
+    fn maximizes_total_priority_not_count() {
+        // 两个低优先级条目总和为4，不如一个优先级5的条目
+        let candidates = [entry(Some(2), 100), entry(Some(2), 100), entry(Some(5), 200)];
+        assert_eq!(selected(&candidates, 200), vec![2]);
+    }
+
+    #[test]
+    fn combines_entries_to_beat_single_high_priority() {
+        let candidates = [entry(Some(10), 1000), entry(Some(6), 400), entry(Some(6), 500)];
+        assert_eq!(selected(&candidates, 1000), vec![1, 2]);
+    }
+
+    #[test]
+    fn prefers_cheaper_combination_on_priority_tie() {
+        let candidates = [entry(Some(4), 300), entry(Some(4), 100)];
+        assert_eq!(selected(&candidates, 300), vec![1]);
+    }
+
+    #[test]
+    fn clamps_out_of_range_priorities() {
+        // 超出范围的优先级按边界值计算：1000按10，-5按1
+        let candidates = [entry(Some(1000), 100), entry(Some(MAX_PRIORITY), 100), entry(Some(-5), 10)];
+        assert_eq!(priority_weight(&candidates[0]), MAX_PRIORITY as usize);
+        assert_eq!(priority_weight(&candidates[2]), MIN_PRIORITY as usize);
+        let chosen = selected(&candidates, 110);
+        assert_eq!(chosen.len(), 2);
+        assert!(chosen.contains(&2));
+    }
+
+    #[test]
+    fn free_entries_are_always_selected() {
+        let candidates = [entry(Some(1), 0), entry(Some(9), 1000)];
+        assert_eq!(selected(&candidates, 0), vec![0]);
+    }
+}
@@ -1,5 +1,5 @@
 // 数据库操作模块
-use rusqlite::{Connection, Result};
+use rusqlite::{params, Connection, OptionalExtension, Result};
 use std::env;
 use std::path::Path;
 use tauri::{App, Manager};
@@ -56,6 +56,7 @@ fn init_common_db(app: &App, db_path: &std::path::Path) -> Result<()> {
                     eprintln!("Failed to execute SQL commands from dev init.sql: {:?}", e);
                     // 如果执行失败，继续使用默认初始化
                 } else {
+                    after_catalog_update(&conn);
                     return Ok(());
                 }
             }
@@ -88,6 +89,7 @@ fn init_common_db(app: &App, db_path: &std::path::Path) -> Result<()> {
                     );
                     // 如果执行失败，继续使用默认初始化
                 } else {
+                    after_catalog_update(&conn);
                     return Ok(());
                 }
             }
@@ -120,6 +122,7 @@ fn init_common_db(app: &App, db_path: &std::path::Path) -> Result<()> {
                     );
                     // 如果执行失败，继续使用默认初始化
                 } else {
+                    after_catalog_update(&conn);
                     return Ok(());
                 }
             }
@@ -136,6 +139,14 @@ fn init_common_db(app: &App, db_path: &std::path::Path) -> Result<()> {
         Err(e) => return Err(e),
     };
 
+    create_catalog_tables(&conn)?;
+    after_catalog_update(&conn);
+
+    Ok(())
+}
+
+// 创建目录表（未使用init.sql初始化时）
+fn create_catalog_tables(conn: &Connection) -> Result<()> {
     // 强制创建所有表，无论它们是否已存在
     // 这样可以确保即使数据库文件存在但表结构不完整，也能被正确修复
 
@@ -204,6 +215,289 @@ fn init_common_db(app: &App, db_path: &std::path::Path) -> Result<()> {
     Ok(())
 }
 
+// 目录更新后记录价格变化和DLC信息，失败时不影响启动
+fn after_catalog_update(conn: &Connection) {
+    if let Err(e) = record_price_changes(conn, false) {
+        eprintln!("Failed to record vehicle price history: {:?}", e);
+    }
+    if let Err(e) = sync_dlc_releases(conn) {
+        eprintln!("Failed to sync dlc releases: {:?}", e);
+    }
+    if let Err(e) = create_brand_meta_table(conn) {
+        eprintln!("Failed to create vehicle_brand_meta table: {:?}", e);
+    }
+    if let Err(e) = create_vehicle_stats_table(conn) {
+        eprintln!("Failed to create vehicle_stats table: {:?}", e);
+    }
+    if let Err(e) = create_vehicle_acquisition_table(conn) {
+        eprintln!("Failed to create vehicle_acquisition table: {:?}", e);
+    }
+    if let Err(e) = create_vehicle_variant_table(conn) {
+        eprintln!("Failed to create vehicle_variant table: {:?}", e);
+    }
+    if let Err(e) = create_vehicle_image_table(conn) {
+        eprintln!("Failed to create vehicle_image table: {:?}", e);
+    }
+    // 乐观并发控制的版本号，init.sql重建载具表后需重新添加
+    if !has_column(conn, "vehicle_overview", "version") {
+        if let Err(e) = conn.execute("ALTER TABLE vehicle_overview ADD COLUMN version INTEGER NOT NULL DEFAULT 0", []) {
+            eprintln!("Failed to add vehicle_overview version column: {:?}", e);
+        }
+    }
+}
+
+// 创建品牌元数据表
+// init.sql每次更新都会重建vehicle_brand，因此品牌的标志、国家等信息另存于该表
+pub(crate) fn create_brand_meta_table(conn: &Connection) -> Result<usize> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_brand_meta (
+            brand_id INTEGER NOT NULL PRIMARY KEY,
+            logo_path TEXT,
+            country TEXT,
+            real_world_brand TEXT,
+            parent_brand_id INTEGER
+        )",
+        [],
+    )
+}
+
+// 创建载具性能数据表，数据来自本地CSV，不随init.sql更新
+pub(crate) fn create_vehicle_stats_table(conn: &Connection) -> Result<usize> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_stats (
+            vehicle_id TEXT NOT NULL PRIMARY KEY,
+            speed REAL,
+            acceleration REAL,
+            braking REAL,
+            handling REAL,
+            lap_time REAL,
+            top_speed REAL,
+            seats INTEGER,
+            drive_type TEXT
+        )",
+        [],
+    )
+}
+
+// 创建载具获取方式表
+// init.sql每次更新都会重建vehicle_overview，因此商店、解锁条件等信息另存于该表
+pub(crate) fn create_vehicle_acquisition_table(conn: &Connection) -> Result<usize> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_acquisition (
+            vehicle_id TEXT NOT NULL PRIMARY KEY,
+            store TEXT,
+            unlock_condition TEXT,
+            obtainability TEXT
+        )",
+        [],
+    )
+}
+
+// 创建改装关系表，记录基础载具可改装成的载具（如本尼、阿浩、伊玛尼改装）
+// upgrade_kind对应feature_type_dict中的特性键
+pub(crate) fn create_vehicle_variant_table(conn: &Connection) -> Result<usize> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_variant (
+            base_id TEXT NOT NULL,
+            variant_id TEXT NOT NULL,
+            upgrade_kind TEXT,
+            upgrade_cost INTEGER,
+            PRIMARY KEY (base_id, variant_id)
+        )",
+        [],
+    )
+}
+
+// 创建载具图片表，图片文件保存在应用数据目录的vehicle_images目录中
+pub(crate) fn create_vehicle_image_table(conn: &Connection) -> Result<usize> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_image (
+            vehicle_id TEXT NOT NULL PRIMARY KEY,
+            file_name TEXT NOT NULL,
+            width INTEGER,
+            height INTEGER,
+            source TEXT
+        )",
+        [],
+    )
+}
+
+// 检查表中是否存在指定字段
+pub(crate) fn has_column(conn: &Connection, table: &str, column: &str) -> bool {
+    let mut stmt = match conn.prepare(&format!("PRAGMA table_info({})", table)) {
+        Ok(stmt) => stmt,
+        Err(_) => return false,
+    };
+    let names: Vec<String> = match stmt.query_map([], |row| row.get::<usize, String>(1)) {
+        Ok(rows) => rows.flatten().collect(),
+        Err(_) => return false,
+    };
+    names.iter().any(|name| name == column)
+}
+
+// 同步DLC发布记录，并为载具补充引入该载具的DLC
+// init.sql每次更新都会重建vehicle_overview，因此载具与DLC的对应关系另存于vehicle_dlc表
+pub(crate) fn sync_dlc_releases(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS dlc_release (
+            id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            dlc_name TEXT,
+            dlc_name_en TEXT,
+            release_date DATE,
+            catalog_version INTEGER
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_dlc (
+            vehicle_id TEXT NOT NULL PRIMARY KEY,
+            dlc_id INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    // 记录载具首次出现时的目录版本，catalog_version为空表示开始记录DLC之前已存在的载具
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS catalog_vehicle (
+            vehicle_id TEXT NOT NULL PRIMARY KEY,
+            catalog_version INTEGER
+        )",
+        [],
+    )?;
+
+    if !has_column(conn, "vehicle_overview", "dlc_id") {
+        conn.execute("ALTER TABLE vehicle_overview ADD COLUMN dlc_id INTEGER", [])?;
+    }
+
+    // data_info中的当前DLC若尚未记录，则作为新的目录版本加入
+    let has_data_info = has_column(conn, "data_info", "dlc_name");
+    if has_data_info {
+        conn.execute(
+            "INSERT INTO dlc_release (dlc_name, dlc_name_en, release_date, catalog_version)
+             SELECT d.dlc_name, d.dlc_name_en, d.update_time,
+                    (SELECT COALESCE(MAX(catalog_version), 0) + 1 FROM dlc_release)
+             FROM (SELECT dlc_name, dlc_name_en, update_time FROM data_info LIMIT 1) d
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM dlc_release r
+                 WHERE r.dlc_name_en IS d.dlc_name_en AND r.release_date IS d.update_time
+             )",
+            [],
+        )?;
+    }
+
+    // 目录中已指定的DLC优先
+    conn.execute(
+        "INSERT OR REPLACE INTO vehicle_dlc (vehicle_id, dlc_id)
+         SELECT id, dlc_id FROM vehicle_overview WHERE dlc_id IS NOT NULL",
+        [],
+    )?;
+
+    // 首次同步时已有的载具无法确定引入的DLC，只记录为已存在，不归入当前DLC
+    let first_sync: bool = conn.query_row("SELECT NOT EXISTS (SELECT 1 FROM catalog_vehicle)", [], |row| row.get(0))?;
+    let current_release: Option<(i32, Option<i64>)> = if has_data_info {
+        conn.query_row(
+            "SELECT r.id, r.catalog_version
+             FROM (SELECT dlc_name_en, update_time FROM data_info LIMIT 1) d
+             JOIN dlc_release r ON r.dlc_name_en IS d.dlc_name_en AND r.release_date IS d.update_time
+             ORDER BY r.id LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+    } else {
+        None
+    };
+
+    // 上次同步之后新出现的载具视为由当前DLC引入
+    if let (false, Some((release_id, _))) = (first_sync, current_release) {
+        conn.execute(
+            "INSERT OR IGNORE INTO vehicle_dlc (vehicle_id, dlc_id)
+             SELECT id, ?1 FROM vehicle_overview
+             WHERE id NOT IN (SELECT vehicle_id FROM catalog_vehicle)",
+            params![release_id],
+        )?;
+    }
+    let first_seen_version = current_release.and_then(|(_, version)| version).filter(|_| !first_sync);
+    conn.execute(
+        "INSERT OR IGNORE INTO catalog_vehicle (vehicle_id, catalog_version) SELECT id, ?1 FROM vehicle_overview",
+        params![first_seen_version],
+    )?;
+
+    conn.execute(
+        "UPDATE vehicle_overview
+         SET dlc_id = (SELECT dlc_id FROM vehicle_dlc WHERE vehicle_dlc.vehicle_id = vehicle_overview.id)
+         WHERE dlc_id IS NULL",
+        [],
+    )?;
+
+    Ok(())
+}
+
+// 创建价格历史表（init.sql不会删除该表，因此目录更新后历史记录仍然保留）
+// price_kind区分价格种类，标准价格以外的种类见price_history_api::PRICE_KINDS
+pub(crate) fn create_price_history_table(conn: &Connection) -> Result<usize> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_price_history (
+            id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            vehicle_id TEXT NOT NULL,
+            price INTEGER NOT NULL,
+            previous_price INTEGER,
+            dlc_name TEXT,
+            effective_date DATE,
+            price_kind TEXT NOT NULL DEFAULT 'standard'
+        )",
+        [],
+    )?;
+    if has_column(conn, "vehicle_price_history", "price_kind") {
+        return Ok(0);
+    }
+    conn.execute(
+        "ALTER TABLE vehicle_price_history ADD COLUMN price_kind TEXT NOT NULL DEFAULT 'standard'",
+        [],
+    )
+}
+
+// 创建载具价格表，保存交易价、促销价、回收价等非标准价格（标准价格仍为vehicle_overview.price）
+pub(crate) fn create_vehicle_price_table(conn: &Connection) -> Result<usize> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_price (
+            vehicle_id TEXT NOT NULL,
+            price_kind TEXT NOT NULL,
+            price INTEGER NOT NULL,
+            PRIMARY KEY (vehicle_id, price_kind)
+        )",
+        [],
+    )
+}
+
+// 将与最近一次历史记录不同的载具价格写入价格历史表
+// 目录更新使用data_info中的更新日期作为生效日期，手动修改使用当天日期
+pub(crate) fn record_price_changes(conn: &Connection, manual: bool) -> Result<usize> {
+    create_price_history_table(conn)?;
+
+    // data_info可能不存在（未使用init.sql初始化时）
+    let (dlc_name, update_time): (Option<String>, Option<String>) = conn
+        .query_row("SELECT dlc_name, update_time FROM data_info LIMIT 1", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap_or((None, None));
+
+    // 价格可能以文本形式存储，只记录能转换为整数的价格
+    conn.execute(
+        "INSERT INTO vehicle_price_history (vehicle_id, price, previous_price, dlc_name, effective_date)
+         SELECT v.id, CAST(v.price AS INTEGER), h.price, ?1,
+                CASE WHEN ?3 THEN date('now') ELSE COALESCE(?2, date('now')) END
+         FROM vehicle_overview v
+         LEFT JOIN vehicle_price_history h
+           ON h.vehicle_id = v.id
+          AND h.id = (SELECT MAX(id) FROM vehicle_price_history WHERE vehicle_id = v.id AND price_kind = 'standard')
+         WHERE v.price IS NOT NULL
+           AND CAST(v.price AS TEXT) = CAST(CAST(v.price AS INTEGER) AS TEXT)
+           AND (h.price IS NULL OR h.price != CAST(v.price AS INTEGER))",
+        params![dlc_name, update_time, manual],
+    )
+}
+
 // 初始化用户数据库（车库）
 fn init_user_db(_app: &App, db_path: &std::path::Path) -> Result<()> {
     // 打开数据库连接
@@ -212,6 +506,11 @@ fn init_user_db(_app: &App, db_path: &std::path::Path) -> Result<()> {
         Err(e) => return Err(e),
     };
 
+    create_user_tables(&conn)
+}
+
+// 创建或升级用户数据表
+fn create_user_tables(conn: &Connection) -> Result<()> {
     // 首先检查表是否存在
     let table_exists: bool = conn.prepare("SELECT name FROM sqlite_master WHERE type='table' AND name='garage_overview'")
         .and_then(|mut stmt| stmt.query_row([], |row| row.get::<usize, String>(0)))
@@ -228,7 +527,8 @@ fn init_user_db(_app: &App, db_path: &std::path::Path) -> Result<()> {
                 vehicle_list TEXT,
                 remarks TEXT,
                 garage_order INTEGER,
-                garage_type VARCHAR(255)
+                garage_type VARCHAR(255),
+                version INTEGER NOT NULL DEFAULT 0
             )",
             [],
         );
@@ -280,7 +580,259 @@ fn init_user_db(_app: &App, db_path: &std::path::Path) -> Result<()> {
                 eprintln!("Failed to add garage_order column: {:?}", e);
             }
         }
+
+        // 检查是否需要添加version字段（乐观并发控制）
+        if !has_column(conn, "garage_overview", "version") {
+            if let Err(e) = conn.execute("ALTER TABLE garage_overview ADD COLUMN version INTEGER NOT NULL DEFAULT 0", []) {
+                eprintln!("Failed to add version column: {:?}", e);
+            }
+        }
+    }
+
+    // 创建愿望清单表，vehicle_id对应通用数据库中的vehicle_overview.id
+    if let Err(e) = conn.execute(
+        "CREATE TABLE IF NOT EXISTS wishlist (
+            id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            vehicle_id TEXT NOT NULL,
+            priority INTEGER,
+            target_garage_id INTEGER,
+            notes TEXT,
+            sort_order INTEGER
+        )",
+        [],
+    ) {
+        eprintln!("Failed to create wishlist table: {:?}", e);
+        return Err(e);
+    }
+
+    // 创建角色档案，车库、愿望清单和设置均归属于某个角色
+    if let Err(e) = migrate_profiles(conn) {
+        eprintln!("Failed to migrate profiles: {:?}", e);
+        return Err(e);
+    }
+
+    // 创建车队成员表，记录导入的其他玩家车库数据（只读副本存放在crew_vaults目录）
+    if let Err(e) = conn.execute(
+        "CREATE TABLE IF NOT EXISTS crew_member (
+            id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            member_name VARCHAR(255) NOT NULL,
+            file_name VARCHAR(255) NOT NULL,
+            imported_at VARCHAR(255)
+        )",
+        [],
+    ) {
+        eprintln!("Failed to create crew_member table: {:?}", e);
+        return Err(e);
+    }
+
+    // 创建活动要求预设表，criteria为JSON格式的筛选条件
+    if let Err(e) = conn.execute(
+        "CREATE TABLE IF NOT EXISTS event_preset (
+            id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            preset_name VARCHAR(255) NOT NULL,
+            criteria TEXT NOT NULL,
+            remarks TEXT
+        )",
+        [],
+    ) {
+        eprintln!("Failed to create event_preset table: {:?}", e);
+        return Err(e);
+    }
+
+    // 创建改装记录表，记录车位中载具的改装及花费
+    if let Err(e) = conn.execute(
+        "CREATE TABLE IF NOT EXISTS vehicle_conversion (
+            id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            profile_id INTEGER,
+            garage_id INTEGER NOT NULL,
+            slot INTEGER NOT NULL,
+            from_vehicle_id TEXT NOT NULL,
+            to_vehicle_id TEXT NOT NULL,
+            upgrade_kind TEXT,
+            upgrade_cost INTEGER,
+            converted_at VARCHAR(255)
+        )",
+        [],
+    ) {
+        eprintln!("Failed to create vehicle_conversion table: {:?}", e);
+        return Err(e);
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// 当前角色的ID，用于在SQL中限定车库、愿望清单和设置的范围
+pub(crate) const ACTIVE_PROFILE: &str = "(SELECT id FROM profile WHERE is_active = 1 ORDER BY id LIMIT 1)";
+
+// 创建角色表和角色设置表，并将已有数据归入默认角色
+fn migrate_profiles(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS profile (
+            id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            profile_name VARCHAR(255) NOT NULL,
+            remarks TEXT,
+            sort_order INTEGER,
+            is_active INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS profile_setting (
+            profile_id INTEGER NOT NULL,
+            setting_key VARCHAR(255) NOT NULL,
+            setting_value TEXT,
+            PRIMARY KEY (profile_id, setting_key)
+        )",
+        [],
+    )?;
+
+    let profile_count: i64 = conn.query_row("SELECT COUNT(*) FROM profile", [], |row| row.get(0))?;
+    if profile_count == 0 {
+        conn.execute(
+            "INSERT INTO profile (profile_name, sort_order, is_active) VALUES ('默认角色', 1, 1)",
+            [],
+        )?;
+    }
+    // 没有当前角色时启用第一个角色
+    conn.execute(
+        "UPDATE profile SET is_active = 1
+         WHERE id = (SELECT MIN(id) FROM profile) AND NOT EXISTS (SELECT 1 FROM profile WHERE is_active = 1)",
+        [],
+    )?;
+
+    for table in ["garage_overview", "wishlist"] {
+        if !has_column(conn, table, "profile_id") {
+            conn.execute(&format!("ALTER TABLE {} ADD COLUMN profile_id INTEGER", table), [])?;
+        }
+        conn.execute(
+            &format!("UPDATE {} SET profile_id = {} WHERE profile_id IS NULL", table, ACTIVE_PROFILE),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+// 测试用的内存数据库，建表代码与正式数据库相同
+#[cfg(test)]
+pub(crate) mod test_support {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    // 内存中的用户库
+    pub(crate) fn user_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        create_user_tables(&conn).unwrap();
+        conn
+    }
+
+    // 内存中的目录库，并与attach_user_db一样以user_db附加用户库
+    // 用户库使用共享缓存的内存库，建表后可被目录库按URI附加
+    pub(crate) fn catalog_db() -> Connection {
+        static NEXT_DB: AtomicUsize = AtomicUsize::new(0);
+        let uri = format!(
+            "file:gtavm_test_user_{}?mode=memory&cache=shared",
+            NEXT_DB.fetch_add(1, Ordering::SeqCst)
+        );
+        let user_conn = Connection::open(&uri).unwrap();
+        create_user_tables(&user_conn).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        create_catalog_tables(&conn).unwrap();
+        after_catalog_update(&conn);
+        create_vehicle_price_table(&conn).unwrap();
+        conn.execute("ATTACH DATABASE ?1 AS user_db", params![uri]).unwrap();
+        conn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog_with_dlc(dlc_name_en: &str, update_time: &str) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        create_catalog_tables(&conn).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE data_info (dlc_name TEXT, dlc_name_en TEXT, update_time DATE);
+             INSERT INTO vehicle_brand (id, brand_name) VALUES (1, 'Pegassi');",
+        )
+        .unwrap();
+        set_data_info(&conn, dlc_name_en, update_time);
+        conn
+    }
+
+    fn set_data_info(conn: &Connection, dlc_name_en: &str, update_time: &str) {
+        conn.execute("DELETE FROM data_info", []).unwrap();
+        conn.execute(
+            "INSERT INTO data_info VALUES (?1, ?1, ?2)",
+            params![dlc_name_en, update_time],
+        )
+        .unwrap();
+    }
+
+    fn add_vehicle(conn: &Connection, id: &str) {
+        conn.execute("INSERT INTO vehicle_overview (id, brand_id) VALUES (?1, 1)", params![id]).unwrap();
+    }
+
+    fn vehicle_dlc(conn: &Connection, id: &str) -> Option<String> {
+        conn.query_row(
+            "SELECT r.dlc_name_en FROM vehicle_dlc d JOIN dlc_release r ON r.id = d.dlc_id WHERE d.vehicle_id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()
+        .unwrap()
+    }
+
+    #[test]
+    fn first_sync_leaves_existing_vehicles_unattributed() {
+        let conn = catalog_with_dlc("Casino", "2019-07-23");
+        add_vehicle(&conn, "1-1");
+        add_vehicle(&conn, "1-2");
+        sync_dlc_releases(&conn).unwrap();
+        assert_eq!(vehicle_dlc(&conn, "1-1"), None);
+        assert_eq!(vehicle_dlc(&conn, "1-2"), None);
+
+        // 再次同步时已记录的载具仍不归入当前DLC
+        sync_dlc_releases(&conn).unwrap();
+        assert_eq!(vehicle_dlc(&conn, "1-1"), None);
+    }
+
+    #[test]
+    fn only_vehicles_new_since_last_sync_are_credited() {
+        let conn = catalog_with_dlc("Casino", "2019-07-23");
+        add_vehicle(&conn, "1-1");
+        sync_dlc_releases(&conn).unwrap();
+
+        set_data_info(&conn, "Heist", "2019-12-12");
+        add_vehicle(&conn, "1-2");
+        sync_dlc_releases(&conn).unwrap();
+        assert_eq!(vehicle_dlc(&conn, "1-1"), None);
+        assert_eq!(vehicle_dlc(&conn, "1-2").as_deref(), Some("Heist"));
+
+        set_data_info(&conn, "Tuners", "2021-07-20");
+        add_vehicle(&conn, "1-3");
+        sync_dlc_releases(&conn).unwrap();
+        assert_eq!(vehicle_dlc(&conn, "1-2").as_deref(), Some("Heist"));
+        assert_eq!(vehicle_dlc(&conn, "1-3").as_deref(), Some("Tuners"));
+        let dlc_id: Option<i32> = conn
+            .query_row("SELECT dlc_id FROM vehicle_overview WHERE id = '1-3'", [], |row| row.get(0))
+            .unwrap();
+        assert!(dlc_id.is_some());
+    }
+
+    #[test]
+    fn catalog_dlc_takes_precedence() {
+        let conn = catalog_with_dlc("Casino", "2019-07-23");
+        sync_dlc_releases(&conn).unwrap();
+        add_vehicle(&conn, "1-1");
+        sync_dlc_releases(&conn).unwrap();
+        conn.execute("INSERT INTO dlc_release (dlc_name_en) VALUES ('Older')", []).unwrap();
+        let older = conn.last_insert_rowid();
+        add_vehicle(&conn, "1-2");
+        conn.execute("UPDATE vehicle_overview SET dlc_id = ?1 WHERE id = '1-2'", params![older]).unwrap();
+        sync_dlc_releases(&conn).unwrap();
+        assert_eq!(vehicle_dlc(&conn, "1-2").as_deref(), Some("Older"));
+    }
+}
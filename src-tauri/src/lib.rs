@@ -4,11 +4,14 @@
 mod api;
 mod database;
 mod models;
+pub mod share_server;
+
+use tauri::Manager;
 
 // 重新导出模块内容
 pub use api::*;
 pub use database::init_db;
-pub use models::{ApiResponse, GarageOverview, VehicleBrand, VehicleOverview, FeatureTypeDict};
+pub use models::{ApiResponse, GarageOverview, VehicleBrand, VehicleOverview, FeatureTypeDict, SlotInstance, UpgradeStatus};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -20,6 +23,21 @@ pub fn run() {
             }
             Ok(())
         })
+        // 载具图片协议，原图和缩略图均从应用数据目录读取
+        // 生成缩略图需要解码原图，在后台线程中处理以免阻塞界面
+        .register_asynchronous_uri_scheme_protocol(IMAGE_SCHEME, |ctx, request, responder| {
+            match ctx.app_handle().path().app_data_dir() {
+                Ok(app_dir) => {
+                    tauri::async_runtime::spawn_blocking(move || {
+                        responder.respond(serve_vehicle_image(&app_dir, &request))
+                    });
+                }
+                Err(_) => {
+                    let response = tauri::http::Response::builder().status(500).body(Vec::new()).unwrap_or_default();
+                    responder.respond(response)
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             // 车库概览管理
@@ -27,11 +45,16 @@ pub fn run() {
             add_garage_overview,
             update_garage_overview,
             delete_garage_overview,
+            resize_garage,
+            // 车位个性化信息
+            get_slot_instance,
+            update_slot_instance,
             // 载具品牌管理
             get_vehicle_brands,
             add_vehicle_brand,
             update_vehicle_brand,
             delete_vehicle_brand,
+            merge_brands,
             // 载具概览管理
             get_vehicle_overviews,
             get_vehicle_overviews_by_brand,
@@ -39,6 +62,8 @@ pub fn run() {
             add_vehicle_overview,
             update_vehicle_overview,
             delete_vehicle_overview,
+            get_next_vehicle_id,
+            rekey_vehicle,
             // 特性类型字典管理
             get_feature_type_dicts,
             get_feature_type_dict_by_key,
@@ -46,7 +71,89 @@ pub fn run() {
             get_vehicle_type_dicts,
             get_vehicle_type_dict_by_key,
             // 数据信息管理
-            get_data_info
+            get_data_info,
+            // 重复载具检测
+            find_duplicates,
+            // 愿望清单管理
+            get_wishlist,
+            add_wishlist_entry,
+            delete_wishlist_entry,
+            reorder_wishlist,
+            plan_budget,
+            // 价格历史
+            get_vehicle_price_history,
+            get_latest_price_changes,
+            // DLC历史
+            get_dlc_releases,
+            add_dlc_release,
+            set_vehicle_dlc,
+            get_vehicle_overviews_by_dlc,
+            // 目录差异比较
+            diff_catalog,
+            // Wiki表格导入
+            import_wiki_tables,
+            // 数据完整性检查
+            check_catalog_integrity,
+            check_user_data,
+            repair_user_data,
+            // 批量编辑目录
+            bulk_update_vehicles,
+            bulk_add_vehicles,
+            // 部分更新（乐观并发控制）
+            patch_garage_overview,
+            patch_vehicle_overview,
+            // 角色管理
+            get_profiles,
+            create_profile,
+            rename_profile,
+            switch_profile,
+            duplicate_profile,
+            delete_profile,
+            get_profile_settings,
+            set_profile_setting,
+            get_profile_stats,
+            // 局域网只读共享
+            start_share_server,
+            stop_share_server,
+            get_share_server_status,
+            // 车库分享码
+            encode_garage_code,
+            preview_garage_code,
+            import_garage_code,
+            // 车队共享
+            export_user_vault,
+            import_crew_vault,
+            get_crew_members,
+            get_crew_member_garages,
+            delete_crew_member,
+            compare_crew_vaults,
+            // 活动载具要求
+            find_owned_vehicles,
+            get_event_presets,
+            save_event_preset,
+            delete_event_preset,
+            // 载具性能数据
+            get_vehicle_stats,
+            import_vehicle_stats_csv,
+            compare_vehicles,
+            // 载具获取方式
+            set_vehicle_acquisition,
+            get_vehicle_stores,
+            // 多种价格与收藏估值
+            get_vehicle_prices,
+            set_vehicle_price,
+            get_collection_valuation,
+            // 改装关系
+            get_vehicle_variants,
+            save_vehicle_variant,
+            delete_vehicle_variant,
+            convert_slot_vehicle,
+            get_vehicle_conversions,
+            // 载具图片
+            get_vehicle_images,
+            import_vehicle_images,
+            delete_vehicle_image,
+            get_missing_vehicle_images
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -11,6 +11,69 @@ pub struct GarageOverview {
     pub remarks: Option<String>,
     pub garage_order: Option<i32>,
     pub garage_type: Option<String>,
+    // 乐观并发控制的版本号，每次写入递增
+    #[serde(default)]
+    pub version: Option<i64>,
+}
+
+// 载具改装状态
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradeStatus {
+    Stock,
+    Partial,
+    Full,
+}
+
+// 车位中载具的个性化信息，plate、notes和intentional_duplicate分别保存在车位的plate、remarks和
+// intentional_duplicate字段中，其余字段保存在instance字段中
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SlotInstance {
+    #[serde(default)]
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub plate: Option<String>,
+    #[serde(default)]
+    pub livery: Option<String>,
+    #[serde(default)]
+    pub upgrade_status: Option<UpgradeStatus>,
+    #[serde(default)]
+    pub purchase_date: Option<String>,
+    #[serde(default)]
+    pub purchase_price: Option<i64>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    // 有意重复购买的载具，重复检查时默认不报告
+    #[serde(default)]
+    pub intentional_duplicate: Option<bool>,
+}
+
+// 车库缩容时溢出载具的处理策略
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    // 存在溢出载具时拒绝缩容
+    Refuse,
+    // 将溢出载具移动到指定车库的空位
+    MoveToGarage { garage_id: i32 },
+    // 将溢出载具按车库顺序移动到其他车库的第一个空位
+    FirstFree,
+}
+
+// 单个载具的移动记录
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SlotMove {
+    pub vehicle_id: Option<String>,
+    pub from_garage_id: i32,
+    pub from_slot: usize,
+    pub to_garage_id: i32,
+    pub to_slot: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResizeGarageResult {
+    pub garage: GarageOverview,
+    pub moves: Vec<SlotMove>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -19,6 +82,19 @@ pub struct VehicleBrand {
     pub brand_name: Option<String>,
     pub brand_name_en: Option<String>,
     pub remarks: Option<String>,
+    #[serde(default)]
+    pub logo_path: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub real_world_brand: Option<String>,
+    #[serde(default)]
+    pub parent_brand_id: Option<i32>,
+    // 以下为查询时统计的只读字段
+    #[serde(default)]
+    pub vehicle_count: Option<i64>,
+    #[serde(default)]
+    pub owned_count: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -31,6 +107,36 @@ pub struct VehicleOverview {
     pub feature: Option<String>,
     pub price: Option<i32>,
     pub remarks: Option<String>,
+    #[serde(default)]
+    pub dlc_id: Option<i32>,
+    #[serde(default)]
+    pub version: Option<i64>,
+    #[serde(default)]
+    pub acquisition: Option<VehicleAcquisition>,
+}
+
+// 载具获取方式：商店、交易价格、解锁条件和可获取状态
+// obtainability取值见acquisition_api::OBTAINABILITY_STATUSES
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VehicleAcquisition {
+    pub store: Option<String>,
+    pub trade_price: Option<i64>,
+    pub unlock_condition: Option<String>,
+    pub obtainability: Option<String>,
+}
+
+// 载具列表的获取方式筛选条件，各条件为空时不限制
+// 没有获取方式数据的载具，其可获取状态按unknown匹配
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct VehicleFilter {
+    #[serde(default)]
+    pub stores: Option<Vec<String>>,
+    #[serde(default)]
+    pub obtainability: Option<Vec<String>>,
+    #[serde(default)]
+    pub requires_unlock: Option<bool>,
+    #[serde(default)]
+    pub has_trade_price: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -47,6 +153,585 @@ pub struct DataInfo {
     pub update_time: Option<String>,
 }
 
+// 重复载具所在的车位
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DuplicateLocation {
+    pub garage_id: i32,
+    pub garage_name: Option<String>,
+    pub slot: usize,
+    pub intentional: bool,
+    pub remarks: Option<String>,
+}
+
+// 占用多个车位的载具
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DuplicateVehicle {
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub vehicle_name_en: Option<String>,
+    pub price: Option<i32>,
+    pub count: usize,
+    pub intentional_count: usize,
+    pub locations: Vec<DuplicateLocation>,
+    pub total_value: i64,
+    pub redundant_value: i64,
+}
+
+// 愿望清单条目，vehicle_name、price和owned为查询时补充的只读字段
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WishlistEntry {
+    pub id: Option<i32>,
+    pub vehicle_id: String,
+    // 优先级，取值1到10
+    pub priority: Option<i32>,
+    pub target_garage_id: Option<i32>,
+    pub notes: Option<String>,
+    pub sort_order: Option<i32>,
+    #[serde(default)]
+    pub vehicle_name: Option<String>,
+    #[serde(default)]
+    pub price: Option<i32>,
+    #[serde(default)]
+    pub owned: Option<bool>,
+}
+
+// 预算规划结果
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BudgetPlan {
+    pub available_money: i64,
+    pub total_cost: i64,
+    pub remaining: i64,
+    pub selected: Vec<WishlistEntry>,
+    pub not_selected: Vec<WishlistEntry>,
+    pub already_owned: Vec<WishlistEntry>,
+    pub unpriced: Vec<WishlistEntry>,
+}
+
+// 载具价格历史记录
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PriceHistoryEntry {
+    pub id: i32,
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub price: i64,
+    pub previous_price: Option<i64>,
+    pub dlc_name: Option<String>,
+    pub effective_date: Option<String>,
+    #[serde(default)]
+    pub price_kind: String,
+}
+
+// DLC发布记录，vehicle_count为查询时统计的载具数量
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DlcRelease {
+    pub id: Option<i32>,
+    pub dlc_name: Option<String>,
+    pub dlc_name_en: Option<String>,
+    pub release_date: Option<String>,
+    pub catalog_version: Option<i32>,
+    #[serde(default)]
+    pub vehicle_count: Option<i64>,
+}
+
+// 单个字段的变化
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+// 载具在两个目录版本之间的变化
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VehicleChange {
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub changes: Vec<FieldChange>,
+}
+
+// 品牌在两个目录版本之间的变化
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BrandChange {
+    pub brand_id: i32,
+    pub brand_name_en: Option<String>,
+    pub changes: Vec<FieldChange>,
+}
+
+// 字典项的变化，old_value为空表示新增，new_value为空表示删除
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DictChange {
+    pub dict: String,
+    pub dict_key: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+// 受目录变化影响的车位
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AffectedSlot {
+    pub garage_id: i32,
+    pub garage_name: Option<String>,
+    pub slot: usize,
+    pub vehicle_id: String,
+    pub change_kind: String,
+}
+
+// 候选目录与当前目录的差异
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CatalogDiff {
+    pub added_vehicles: Vec<VehicleOverview>,
+    pub removed_vehicles: Vec<VehicleOverview>,
+    pub modified_vehicles: Vec<VehicleChange>,
+    pub added_brands: Vec<VehicleBrand>,
+    pub removed_brands: Vec<VehicleBrand>,
+    pub modified_brands: Vec<BrandChange>,
+    pub dict_changes: Vec<DictChange>,
+    pub affected_slots: Vec<AffectedSlot>,
+}
+
+// Wiki表格中与现有载具匹配的行
+// 品牌与Wiki不一致时proposed_id为按Wiki品牌分配的新ID，需通过rekey_vehicle修改
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WikiMatch {
+    pub vehicle_id: String,
+    pub vehicle_name_en: Option<String>,
+    pub current_brand_id: Option<i32>,
+    pub wiki_brand_id: i32,
+    pub source_file: String,
+    #[serde(default)]
+    pub proposed_id: Option<String>,
+}
+
+// Wiki表格中尚未收录的载具
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WikiNewVehicle {
+    pub proposed_id: String,
+    pub vehicle_name_en: String,
+    pub brand_id: i32,
+    pub brand_name_en: Option<String>,
+    pub style: Option<String>,
+    pub influence: Option<String>,
+    pub notes: Option<String>,
+    pub source_file: String,
+}
+
+// Wiki导入结果，patch_sql为可审阅后执行的目录补丁
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WikiImportReport {
+    pub matched: Vec<WikiMatch>,
+    pub brand_assignments: Vec<WikiMatch>,
+    pub new_vehicles: Vec<WikiNewVehicle>,
+    pub unknown_brand_files: Vec<String>,
+    pub patch_sql: String,
+}
+
+// 数据完整性问题，fixed表示已在自动修复模式下修复
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IntegrityIssue {
+    pub kind: String,
+    #[serde(default)]
+    pub garage_id: Option<i32>,
+    pub vehicle_id: Option<String>,
+    pub detail: String,
+    pub fixed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IntegrityReport {
+    pub issues: Vec<IntegrityIssue>,
+    pub fixed_count: usize,
+    #[serde(default)]
+    pub backup_path: Option<String>,
+}
+
+// 品牌合并结果，moved_vehicles为原载具ID，rekeyed为按目标品牌重新分配的ID
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BrandMergeReport {
+    pub moved_vehicles: Vec<String>,
+    pub rekeyed: Vec<RekeyReport>,
+    pub updated_slots: Vec<AffectedSlot>,
+}
+
+// 品牌删除结果，blocking_slots为阻止删除的已拥有载具所在车位
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BrandDeleteReport {
+    pub deleted_vehicles: Vec<String>,
+    pub blocking_slots: Vec<AffectedSlot>,
+}
+
+// 载具ID重编号结果
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RekeyReport {
+    pub old_id: String,
+    pub new_id: String,
+    pub updated_tables: Vec<String>,
+    pub updated_slots: Vec<AffectedSlot>,
+}
+
+// 载具部分字段更新，未提供的字段保持不变
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct VehiclePatch {
+    pub id: String,
+    #[serde(default)]
+    pub brand_id: Option<i32>,
+    #[serde(default)]
+    pub vehicle_name: Option<String>,
+    #[serde(default)]
+    pub vehicle_name_en: Option<String>,
+    #[serde(default)]
+    pub vehicle_type: Option<String>,
+    #[serde(default)]
+    pub feature: Option<String>,
+    #[serde(default)]
+    pub price: Option<i32>,
+    #[serde(default)]
+    pub remarks: Option<String>,
+    #[serde(default)]
+    pub dlc_id: Option<i32>,
+    // 读取时的版本号，与当前版本不一致则拒绝写入；批量更新设置ignore_versions时可省略
+    #[serde(default)]
+    pub version: Option<i64>,
+}
+
+// 车库部分字段更新，version为客户端读取时的版本号
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GaragePatch {
+    pub id: i32,
+    pub version: i64,
+    #[serde(default)]
+    pub garage_name: Option<String>,
+    #[serde(default)]
+    pub garage_name_en: Option<String>,
+    #[serde(default)]
+    pub num: Option<i32>,
+    #[serde(default)]
+    pub vehicle_list: Option<String>,
+    #[serde(default)]
+    pub remarks: Option<String>,
+    #[serde(default)]
+    pub garage_order: Option<i32>,
+    #[serde(default)]
+    pub garage_type: Option<String>,
+}
+
+// 批量操作中单行的处理结果，id为处理后的载具ID（新增时可能为自动分配的ID）
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BulkRowResult {
+    pub index: usize,
+    pub id: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+// 批量操作结果，committed为false表示因全部回滚而未写入任何数据
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BulkResult {
+    pub results: Vec<BulkRowResult>,
+    pub applied: usize,
+    pub committed: bool,
+}
+
+// 角色档案（对应游戏中的角色或账号），车库、愿望清单和设置均归属于角色
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Profile {
+    pub id: Option<i32>,
+    pub profile_name: String,
+    #[serde(default)]
+    pub remarks: Option<String>,
+    #[serde(default)]
+    pub sort_order: Option<i32>,
+    #[serde(default)]
+    pub is_active: bool,
+}
+
+// 单个角色的统计
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProfileStats {
+    pub profile_id: i32,
+    pub profile_name: String,
+    pub garage_count: usize,
+    pub capacity: i64,
+    pub vehicle_count: usize,
+    pub wishlist_count: usize,
+    pub total_value: i64,
+}
+
+// 所有角色的汇总统计，unique_vehicle_count为跨角色去重后的载具数量
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProfileStatsReport {
+    pub profiles: Vec<ProfileStats>,
+    pub garage_count: usize,
+    pub capacity: i64,
+    pub vehicle_count: usize,
+    pub unique_vehicle_count: usize,
+    pub wishlist_count: usize,
+    pub total_value: i64,
+}
+
+// 局域网共享服务状态
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShareServerStatus {
+    pub running: bool,
+    pub address: Option<String>,
+}
+
+// 车库分享码中的一个车位，known表示载具存在于当前目录
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GarageCodeSlot {
+    pub slot: usize,
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub known: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GarageCodeGarage {
+    pub garage_name: Option<String>,
+    pub garage_type: Option<String>,
+    pub num: i32,
+    pub slots: Vec<GarageCodeSlot>,
+}
+
+// 车库分享码的预览
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GarageCodePreview {
+    pub version: u8,
+    pub garages: Vec<GarageCodeGarage>,
+    pub unknown_vehicle_ids: Vec<String>,
+}
+
+// 导入的车队成员（只读的外部车库数据）
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CrewMember {
+    pub id: i32,
+    pub member_name: String,
+    pub file_name: String,
+    pub imported_at: Option<String>,
+    #[serde(default)]
+    pub garage_count: usize,
+    #[serde(default)]
+    pub vehicle_count: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CrewVehicle {
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub vehicle_name_en: Option<String>,
+    pub price: Option<i32>,
+}
+
+// 部分成员拥有的载具
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CrewOwnership {
+    pub vehicle: CrewVehicle,
+    pub owners: Vec<String>,
+    pub lacking: Vec<String>,
+}
+
+// 无法读取车库数据的车队成员，error为读取失败的原因
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UnreadableCrewMember {
+    pub member_id: i32,
+    pub member_name: String,
+    pub error: String,
+}
+
+// 车队对比结果，crew_missing为所有成员都没有的目录载具
+// 无法读取的成员不参与对比，列在unreadable_members中
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CrewComparison {
+    pub members: Vec<String>,
+    pub partially_owned: Vec<CrewOwnership>,
+    pub crew_missing: Vec<CrewVehicle>,
+    pub unreadable_members: Vec<UnreadableCrewMember>,
+}
+
+// 活动载具要求，各条件为空时不限制
+// vehicle_types匹配载具类型的中文名或英文名，features要求同时具备所有特性
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct OwnedVehicleCriteria {
+    #[serde(default)]
+    pub vehicle_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub features: Option<Vec<String>>,
+    #[serde(default)]
+    pub brand_ids: Option<Vec<i32>>,
+    #[serde(default)]
+    pub include_crew: Option<bool>,
+}
+
+// 符合活动要求的已拥有载具及其所在车位，crew_member_id为空表示自己的车库
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OwnedVehicleMatch {
+    pub owner: String,
+    pub crew_member_id: Option<i32>,
+    pub garage_id: i32,
+    pub garage_name: Option<String>,
+    pub slot: usize,
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub vehicle_name_en: Option<String>,
+    pub vehicle_type: Option<String>,
+    pub feature: Option<String>,
+    pub brand_id: Option<i32>,
+}
+
+// 已拥有载具的查找结果，unreadable_members为无法读取车库的车队成员
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OwnedVehicleSearch {
+    pub matches: Vec<OwnedVehicleMatch>,
+    pub unreadable_members: Vec<UnreadableCrewMember>,
+}
+
+// 保存的活动要求预设
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EventPreset {
+    pub id: Option<i32>,
+    pub preset_name: String,
+    pub criteria: OwnedVehicleCriteria,
+    pub remarks: Option<String>,
+}
+
+// 载具性能数据
+// speed、acceleration、braking、handling为官方分类属性，lap_time（秒）和top_speed（km/h）为实测数据
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VehicleStats {
+    pub vehicle_id: String,
+    pub speed: Option<f64>,
+    pub acceleration: Option<f64>,
+    pub braking: Option<f64>,
+    pub handling: Option<f64>,
+    pub lap_time: Option<f64>,
+    pub top_speed: Option<f64>,
+    pub seats: Option<i32>,
+    pub drive_type: Option<String>,
+}
+
+// 归一化后的性能数据，按目录中的最小值和最大值换算到0-100，越大越好（圈速越短得分越高）
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct NormalizedVehicleStats {
+    pub speed: Option<f64>,
+    pub acceleration: Option<f64>,
+    pub braking: Option<f64>,
+    pub handling: Option<f64>,
+    pub lap_time: Option<f64>,
+    pub top_speed: Option<f64>,
+}
+
+// 载具对比中的一列，stats为空表示没有性能数据
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VehicleStatsComparison {
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub vehicle_name_en: Option<String>,
+    pub vehicle_type: Option<String>,
+    pub stats: Option<VehicleStats>,
+    pub normalized: Option<NormalizedVehicleStats>,
+}
+
+// CSV导入中被跳过的行，line从1开始（包含表头）
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CsvRowError {
+    pub line: usize,
+    pub error: String,
+}
+
+// 性能数据导入结果
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StatsImportReport {
+    pub imported: usize,
+    pub skipped: Vec<CsvRowError>,
+}
+
+// 载具各种类的当前价格，没有该种类价格时为空
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VehiclePrices {
+    pub vehicle_id: String,
+    pub standard: Option<i64>,
+    pub trade: Option<i64>,
+    pub sale: Option<i64>,
+    pub sell_back: Option<i64>,
+}
+
+// 按指定价格种类估算的收藏价值
+// fallback_count为缺少该种类价格而改用标准价格的车位数，missing_vehicle_ids为最终仍没有价格的载具
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CollectionValuation {
+    pub price_basis: String,
+    pub total_value: i64,
+    pub vehicle_count: usize,
+    pub fallback_count: usize,
+    pub missing_vehicle_ids: Vec<String>,
+}
+
+// 改装关系，base_name和variant_name为查询时补充的只读字段
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VehicleVariant {
+    pub base_id: String,
+    pub variant_id: String,
+    pub upgrade_kind: Option<String>,
+    pub upgrade_cost: Option<i64>,
+    #[serde(default)]
+    pub base_name: Option<String>,
+    #[serde(default)]
+    pub variant_name: Option<String>,
+}
+
+// 车位中载具的改装记录
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VehicleConversion {
+    pub id: i32,
+    pub garage_id: i32,
+    pub slot: usize,
+    pub from_vehicle_id: String,
+    pub to_vehicle_id: String,
+    pub upgrade_kind: Option<String>,
+    pub upgrade_cost: Option<i64>,
+    pub converted_at: Option<String>,
+    // 改装后车库的版本号，只在改装时返回
+    #[serde(default)]
+    pub garage_version: Option<i64>,
+}
+
+// 载具图片，file_name为vehicle_images目录下的原图文件名
+// url和thumbnail_url为查询时生成的自定义协议地址
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VehicleImage {
+    pub vehicle_id: String,
+    pub file_name: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub thumbnail_url: String,
+}
+
+// 图片导入中被跳过的文件
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImageImportSkip {
+    pub file: String,
+    pub reason: String,
+}
+
+// 图片导入结果
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImageImportReport {
+    pub imported: Vec<String>,
+    pub skipped: Vec<ImageImportSkip>,
+}
+
+// 缺少图片的载具，reason为no_image（未导入）或file_missing（文件已丢失）
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MissingVehicleImage {
+    pub vehicle_id: String,
+    pub vehicle_name: Option<String>,
+    pub vehicle_name_en: Option<String>,
+    pub reason: String,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ApiResponse<T> {
     pub success: bool,
@@ -0,0 +1,337 @@
+// 局域网只读共享服务
+// 使用标准库实现的简易HTTP服务，只提供车库、载具、品牌和数据信息的只读JSON接口
+
+// 导入必要的依赖
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+
+// 导入数据模型
+use crate::api::brand_api::{count_owned_by_brand, load_vehicle_brands};
+use crate::api::data_info_api::load_data_info;
+use crate::api::garage_api::load_garages;
+use crate::api::vehicle_api::load_vehicle_overviews;
+use crate::models::ApiResponse;
+
+// 请求头的最大长度，超过时拒绝请求
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+// 同时处理的最大连接数，超过时直接返回503
+const MAX_CONNECTIONS: usize = 16;
+
+// 占用一个连接名额，处理结束时释放
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn acquire(active: &Arc<AtomicUsize>) -> Option<ConnectionSlot> {
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+        Some(ConnectionSlot(Arc::clone(active)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// 运行中的共享服务
+pub struct ShareServer {
+    address: SocketAddr,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ShareServer {
+    // 在指定地址启动共享服务，端口为0时由系统分配
+    pub fn start(app_dir: PathBuf, bind_address: &str, token: String) -> io::Result<ShareServer> {
+        if token.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "共享令牌不能为空"));
+        }
+        let address = bind_address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "无效的监听地址"))?;
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let stop_flag = Arc::clone(&stop);
+        let token = Arc::new(token);
+        let app_dir = Arc::new(app_dir);
+        let active = Arc::new(AtomicUsize::new(0));
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let slot = match ConnectionSlot::acquire(&active) {
+                    Some(slot) => slot,
+                    None => {
+                        let _ = stream.set_write_timeout(Some(Duration::from_secs(1)));
+                        let _ = write_response(&mut stream, &error_response(503, "Service Unavailable", "连接过多，请稍后重试"));
+                        continue;
+                    }
+                };
+                let token = Arc::clone(&token);
+                let app_dir = Arc::clone(&app_dir);
+                thread::spawn(move || {
+                    let _slot = slot;
+                    if let Err(e) = handle_connection(stream, &app_dir, &token) {
+                        eprintln!("Share server request failed: {:?}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(ShareServer {
+            address,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    // 实际监听的地址
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    // 停止共享服务并等待监听线程退出
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // 连接一次以唤醒阻塞在accept上的监听线程
+        let wake_address = match self.address {
+            SocketAddr::V4(addr) if addr.ip().is_unspecified() => SocketAddr::from(([127, 0, 0, 1], addr.port())),
+            SocketAddr::V6(addr) if addr.ip().is_unspecified() => SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], addr.port())),
+            address => address,
+        };
+        let _ = TcpStream::connect_timeout(&wake_address, Duration::from_secs(1));
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ShareServer {
+    fn drop(&mut self) {
+        if self.handle.is_some() {
+            self.shutdown();
+        }
+    }
+}
+
+// HTTP响应
+struct Response {
+    status: u16,
+    reason: &'static str,
+    body: String,
+}
+
+fn json_response<T: Serialize>(status: u16, reason: &'static str, body: &ApiResponse<T>) -> Response {
+    Response {
+        status,
+        reason,
+        body: serde_json::to_string(body).unwrap_or_else(|_| "{}".to_string()),
+    }
+}
+
+fn error_response(status: u16, reason: &'static str, error: &str) -> Response {
+    json_response::<()>(
+        status,
+        reason,
+        &ApiResponse {
+            success: false,
+            data: None,
+            error: Some(error.to_string()),
+        },
+    )
+}
+
+// 与Tauri命令相同的返回格式，查询失败时返回500
+fn api_response<T: Serialize>(result: Result<T, String>) -> Response {
+    match result {
+        Ok(data) => json_response(
+            200,
+            "OK",
+            &ApiResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            },
+        ),
+        Err(e) => error_response(500, "Internal Server Error", &e),
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, app_dir: &Path, token: &str) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+    let response = match read_request_head(&mut stream)? {
+        Some(head) => route(&head, app_dir, token),
+        None => error_response(400, "Bad Request", "无效的请求"),
+    };
+    write_response(&mut stream, &response)
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+// 读取请求行和请求头，只读接口不需要请求体
+fn read_request_head(stream: &mut TcpStream) -> io::Result<Option<String>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    loop {
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+            break;
+        }
+        if buffer.len() > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+    }
+    Ok(String::from_utf8(buffer).ok())
+}
+
+// 从Authorization: Bearer请求头或token查询参数中获取令牌
+// Bearer不区分大小写，查询参数中的令牌按URL编码解码
+fn request_token(head: &str, query: Option<&str>) -> Option<String> {
+    let header_token = head.lines().skip(1).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if !name.trim().eq_ignore_ascii_case("authorization") {
+            return None;
+        }
+        let (scheme, token) = value.trim().split_once(' ')?;
+        scheme
+            .eq_ignore_ascii_case("bearer")
+            .then(|| token.trim().to_string())
+    });
+    header_token.or_else(|| {
+        query?
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .and_then(percent_decode)
+    })
+}
+
+// 解码查询参数中的%XX和+，编码无效或解码结果不是UTF-8时返回None
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let hex = bytes.get(index + 1..index + 3)?;
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return None;
+                }
+                decoded.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+                index += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+// 比较令牌时不因第一个不同的字符提前返回
+fn token_matches(expected: &str, actual: &str) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .bytes()
+            .zip(actual.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn route(head: &str, app_dir: &Path, token: &str) -> Response {
+    let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("");
+    let target = request_line.next().unwrap_or("");
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (target, None),
+    };
+
+    if method != "GET" {
+        return error_response(405, "Method Not Allowed", "只支持GET请求");
+    }
+    match request_token(head, query) {
+        Some(actual) if token_matches(token, &actual) => {}
+        _ => return error_response(401, "Unauthorized", "共享令牌无效"),
+    }
+
+    match path {
+        "/api/garages" => api_response(open_read_only(&app_dir.join("gtavm_user.db")).and_then(|conn| {
+            load_garages(&conn).map_err(|e| e.to_string())
+        })),
+        "/api/vehicles" => api_response(open_read_only(&app_dir.join("gtavm_common.db")).and_then(|conn| {
+            load_vehicle_overviews(&conn).map_err(|e| e.to_string())
+        })),
+        "/api/brands" => api_response(open_read_only(&app_dir.join("gtavm_common.db")).and_then(|conn| {
+            let mut brands = load_vehicle_brands(&conn).map_err(|e| e.to_string())?;
+            let owned_counts = open_read_only(&app_dir.join("gtavm_user.db"))
+                .map(|user_conn| count_owned_by_brand(&conn, &user_conn))
+                .unwrap_or_default();
+            for brand in &mut brands {
+                brand.owned_count = Some(owned_counts.get(&brand.id).copied().unwrap_or(0));
+            }
+            Ok(brands)
+        })),
+        "/api/data-info" => {
+            match open_read_only(&app_dir.join("gtavm_common.db"))
+                .and_then(|conn| load_data_info(&conn).map_err(|e| e.to_string()))
+            {
+                Ok(Some(data_info)) => api_response(Ok(data_info)),
+                Ok(None) => error_response(404, "Not Found", "No data found"),
+                Err(e) => error_response(500, "Internal Server Error", &e),
+            }
+        }
+        _ => error_response(404, "Not Found", "接口不存在"),
+    }
+}
+
+// 共享服务只以只读方式打开数据库
+fn open_read_only(db_path: &Path) -> Result<Connection, String> {
+    Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,230 @@
+// 局域网共享服务的集成测试，在本机随机端口上启动服务并发送HTTP请求
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+
+use gtavm_lib::share_server::ShareServer;
+use rusqlite::Connection;
+use serde_json::Value;
+
+const TOKEN: &str = "crew-secret";
+
+// 创建包含最小目录和车库数据的临时应用数据目录
+fn create_app_dir(name: &str) -> PathBuf {
+    let app_dir = std::env::temp_dir().join(format!("gtavm_share_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&app_dir);
+    fs::create_dir_all(&app_dir).unwrap();
+
+    let common = Connection::open(app_dir.join("gtavm_common.db")).unwrap();
+    common
+        .execute_batch(
+            "CREATE TABLE vehicle_brand (id INTEGER PRIMARY KEY, brand_name TEXT, brand_name_en TEXT, remarks TEXT);
+             CREATE TABLE vehicle_overview (id TEXT PRIMARY KEY, brand_id INTEGER, vehicle_name TEXT, vehicle_name_en TEXT,
+                 vehicle_type TEXT, feature TEXT, price INTEGER, remarks TEXT, dlc_id INTEGER, version INTEGER NOT NULL DEFAULT 0);
+             CREATE TABLE data_info (dlc_name TEXT, dlc_name_en TEXT, update_time TEXT);
+             INSERT INTO vehicle_brand VALUES (1, '佩嘉西', 'Pegassi', NULL), (2, '格罗蒂', 'Grotti', NULL);
+             INSERT INTO vehicle_overview (id, brand_id, vehicle_name, vehicle_name_en, price)
+                 VALUES ('1-1', 1, '捷豹', 'Zentorno', 725000), ('2-1', 2, '图里斯莫R', 'Turismo R', 500000);
+             INSERT INTO data_info VALUES ('佣兵行动', 'Mercenaries', '2023-06-13');",
+        )
+        .unwrap();
+
+    let user = Connection::open(app_dir.join("gtavm_user.db")).unwrap();
+    user.execute_batch(
+        "CREATE TABLE profile (id INTEGER PRIMARY KEY AUTOINCREMENT, profile_name TEXT NOT NULL, remarks TEXT,
+             sort_order INTEGER, is_active INTEGER NOT NULL DEFAULT 0);
+         CREATE TABLE garage_overview (id INTEGER PRIMARY KEY AUTOINCREMENT, garage_name TEXT, garage_name_en TEXT, num INTEGER,
+             vehicle_list TEXT, remarks TEXT, garage_order INTEGER, garage_type TEXT, version INTEGER NOT NULL DEFAULT 0,
+             profile_id INTEGER);
+         INSERT INTO profile (profile_name, sort_order, is_active) VALUES ('主角色', 1, 1), ('副角色', 2, 0);
+         INSERT INTO garage_overview (garage_name, num, vehicle_list, garage_order, profile_id)
+             VALUES ('办公室车库', 2, '[{\"id\":\"1-1\"},{}]', 1, 1),
+                    ('副角色车库', 1, '[{\"id\":\"2-1\"}]', 1, 2);",
+    )
+    .unwrap();
+
+    app_dir
+}
+
+fn start_server(name: &str) -> (ShareServer, PathBuf) {
+    let app_dir = create_app_dir(name);
+    let server = ShareServer::start(app_dir.clone(), "127.0.0.1:0", TOKEN.to_string()).unwrap();
+    (server, app_dir)
+}
+
+// 发送请求并返回状态码和解析后的JSON
+fn request(address: SocketAddr, method: &str, path: &str, token: Option<&str>) -> (u16, Value) {
+    let auth = token
+        .map(|token| format!("Authorization: Bearer {}\r\n", token))
+        .unwrap_or_default();
+    send_raw(
+        address,
+        &format!("{} {} HTTP/1.1\r\nHost: localhost\r\n{}Connection: close\r\n\r\n", method, path, auth),
+    )
+}
+
+// 发送原始请求文本
+fn send_raw(address: SocketAddr, raw_request: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.write_all(raw_request.as_bytes()).unwrap();
+    read_response(stream)
+}
+
+fn read_response(mut stream: TcpStream) -> (u16, Value) {
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).unwrap();
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn rejects_missing_or_wrong_token() {
+    let (server, app_dir) = start_server("token");
+    let address = server.local_addr();
+
+    let (status, body) = request(address, "GET", "/api/garages", None);
+    assert_eq!(status, 401);
+    assert_eq!(body["success"], false);
+
+    let (status, _) = request(address, "GET", "/api/garages", Some("wrong"));
+    assert_eq!(status, 401);
+
+    let (status, _) = request(address, "GET", &format!("/api/garages?token={}", TOKEN), None);
+    assert_eq!(status, 200);
+
+    server.stop();
+    let _ = fs::remove_dir_all(app_dir);
+}
+
+#[test]
+fn serves_read_only_endpoints() {
+    let (server, app_dir) = start_server("endpoints");
+    let address = server.local_addr();
+
+    // 只返回当前角色的车库
+    let (status, body) = request(address, "GET", "/api/garages", Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(body["success"], true);
+    let garages = body["data"].as_array().unwrap();
+    assert_eq!(garages.len(), 1);
+    assert_eq!(garages[0]["garage_name"], "办公室车库");
+
+    let (status, body) = request(address, "GET", "/api/vehicles", Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+    let (status, body) = request(address, "GET", "/api/brands", Some(TOKEN));
+    assert_eq!(status, 200);
+    let brands = body["data"].as_array().unwrap();
+    let pegassi = brands.iter().find(|brand| brand["id"] == 1).unwrap();
+    assert_eq!(pegassi["vehicle_count"], 1);
+    assert_eq!(pegassi["owned_count"], 1);
+
+    let (status, body) = request(address, "GET", "/api/data-info", Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["dlc_name_en"], "Mercenaries");
+
+    server.stop();
+    let _ = fs::remove_dir_all(app_dir);
+}
+
+#[test]
+fn rejects_writes_and_unknown_paths() {
+    let (server, app_dir) = start_server("methods");
+    let address = server.local_addr();
+
+    let (status, _) = request(address, "POST", "/api/garages", Some(TOKEN));
+    assert_eq!(status, 405);
+
+    let (status, _) = request(address, "GET", "/api/unknown", Some(TOKEN));
+    assert_eq!(status, 404);
+
+    server.stop();
+    let _ = fs::remove_dir_all(app_dir);
+}
+
+#[test]
+fn stop_releases_the_port() {
+    let (server, app_dir) = start_server("stop");
+    let address = server.local_addr();
+    server.stop();
+
+    let restarted = ShareServer::start(app_dir.clone(), &address.to_string(), TOKEN.to_string()).unwrap();
+    let (status, _) = request(restarted.local_addr(), "GET", "/api/vehicles", Some(TOKEN));
+    assert_eq!(status, 200);
+
+    restarted.stop();
+    let _ = fs::remove_dir_all(app_dir);
+}
+
+#[test]
+fn decodes_query_token_and_accepts_lowercase_bearer() {
+    let app_dir = create_app_dir("decode");
+    let server = ShareServer::start(app_dir.clone(), "127.0.0.1:0", "crew secret+&/".to_string()).unwrap();
+    let address = server.local_addr();
+
+    // 空格可编码为+或%20，十六进制大小写均可
+    for query in ["crew+secret%2B%26%2F", "crew%20secret%2b%26%2f"] {
+        let (status, _) = request(address, "GET", &format!("/api/vehicles?token={}", query), None);
+        assert_eq!(status, 200, "{}", query);
+    }
+    // 未编码的+按空格解码，无效的编码直接拒绝
+    for query in ["crew+secret+%26%2F", "crew+secret%2B%26%zz"] {
+        let (status, _) = request(address, "GET", &format!("/api/vehicles?token={}", query), None);
+        assert_eq!(status, 401, "{}", query);
+    }
+
+    let (status, _) = send_raw(
+        address,
+        "GET /api/vehicles HTTP/1.1\r\nHost: localhost\r\nauthorization: bearer crew secret+&/\r\nConnection: close\r\n\r\n",
+    );
+    assert_eq!(status, 200);
+
+    server.stop();
+    let _ = fs::remove_dir_all(app_dir);
+}
+
+#[test]
+fn rejects_connections_over_the_limit() {
+    let (server, app_dir) = start_server("limit");
+    let address = server.local_addr();
+
+    // 占满服务端的16个连接名额，空闲连接在读取超时前一直占用名额
+    let idle: Vec<TcpStream> = (0..16).map(|_| TcpStream::connect(address).unwrap()).collect();
+    let (status, body) = read_response(TcpStream::connect(address).unwrap());
+    assert_eq!(status, 503);
+    assert_eq!(body["success"], false);
+
+    // 空闲连接关闭后名额释放
+    drop(idle);
+    let mut status = 0;
+    for _ in 0..50 {
+        status = request(address, "GET", "/api/vehicles", Some(TOKEN)).0;
+        if status == 200 {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    assert_eq!(status, 200);
+
+    server.stop();
+    let _ = fs::remove_dir_all(app_dir);
+}
+
+#[test]
+fn brands_do_not_create_missing_user_database() {
+    let (server, app_dir) = start_server("readonly");
+    let address = server.local_addr();
+    fs::remove_file(app_dir.join("gtavm_user.db")).unwrap();
+
+    let (status, body) = request(address, "GET", "/api/brands", Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(body["data"][0]["owned_count"], 0);
+    assert!(!app_dir.join("gtavm_user.db").exists());
+
+    server.stop();
+    let _ = fs::remove_dir_all(app_dir);
+}